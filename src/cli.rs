@@ -0,0 +1,376 @@
+use crate::data::DataManager;
+use crate::data::manager::Point;
+use crate::data::validate::Severity;
+use crate::export::batch::{BatchExporter, Outcome};
+use crate::export::pdf::Engine;
+use crate::export::text::{self, TextRenderer};
+use crate::export::{self, Destination, Exporter, Format, TemplateSource};
+use crate::tui;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Debug, Parser)]
+#[command(name = "resume-utd", version, about = "A 'up-to-date' resume generator")]
+pub struct Cli {
+    /// Resume data file to read and write
+    #[arg(short, long, global = true, default_value = "data/resume.json")]
+    pub data: String,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Open the interactive editor (default)
+    Tui {
+        /// Keep the undo history in `<data>.history.json` so edits can be
+        /// undone in later sessions
+        #[arg(long)]
+        keep_history: bool,
+    },
+    /// Render the resume without opening the editor
+    Export {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "latex")]
+        format: FormatArg,
+        /// Handlebars template to render [default: templates/resume.<ext>.hbs, else built-in]
+        #[arg(short, long)]
+        template: Option<PathBuf>,
+        /// Where to write the rendered file, or "-" for stdout [default: output/resume[-<profile>].<ext>];
+        /// with --all, the output directory [default: output]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Line width for plain-text output
+        #[arg(long, default_value_t = text::DEFAULT_WIDTH)]
+        width: usize,
+        /// Compile the LaTeX output to a PDF next to it
+        #[arg(long)]
+        pdf: bool,
+        /// LaTeX engine for --pdf [default: first of xelatex, lualatex, pdflatex, tectonic on PATH]
+        #[arg(long, value_enum)]
+        engine: Option<EngineArg>,
+        /// Render the named profile from the data file instead of everything
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Render every profile in every format into <output>/<profile>/resume.<ext>,
+        /// skipping targets whose data and template are unchanged
+        #[arg(long, conflicts_with_all = ["format", "template", "profile"])]
+        all: bool,
+        /// With --all, re-render targets even if they are unchanged
+        #[arg(long, requires = "all")]
+        force: bool,
+    },
+    /// Check the resume for problems and that the template renders; exits non-zero on errors
+    Validate {
+        /// Output format whose template to test-render
+        #[arg(short, long, value_enum, default_value = "latex")]
+        format: FormatArg,
+        /// Handlebars template to test-render [default: templates/resume.<ext>.hbs, else built-in]
+        #[arg(short, long)]
+        template: Option<PathBuf>,
+        /// Also fail on warnings
+        #[arg(long)]
+        strict: bool,
+        /// Test-render the named profile
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Write the built-in template to disk as a starting point for customization
+    Template {
+        /// Format of the template to write
+        #[arg(short, long, value_enum, default_value = "latex")]
+        format: FormatArg,
+        /// Where to write it [default: templates/resume.<ext>.hbs]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Convert a JSON Resume (jsonresume.org) file into the data file
+    Import {
+        /// JSON Resume file to read
+        input: PathBuf,
+        /// Overwrite an existing data file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Rewrite the data file as JSON, YAML or TOML, chosen by the output extension
+    Convert {
+        /// File to write, e.g. data/resume.yaml
+        output: PathBuf,
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the entries of one resume section
+    List {
+        #[arg(value_enum)]
+        section: Section,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum FormatArg {
+    Latex,
+    Markdown,
+    Html,
+    Text,
+    JsonResume,
+}
+
+impl From<FormatArg> for Format {
+    fn from(arg: FormatArg) -> Self {
+        match arg {
+            FormatArg::Latex => Format::Latex,
+            FormatArg::Markdown => Format::Markdown,
+            FormatArg::Html => Format::Html,
+            FormatArg::Text => Format::Text,
+            FormatArg::JsonResume => Format::JsonResume,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum EngineArg {
+    Xelatex,
+    Lualatex,
+    Pdflatex,
+    Tectonic,
+}
+
+impl From<EngineArg> for Engine {
+    fn from(arg: EngineArg) -> Self {
+        match arg {
+            EngineArg::Xelatex => Engine::Xelatex,
+            EngineArg::Lualatex => Engine::Lualatex,
+            EngineArg::Pdflatex => Engine::Pdflatex,
+            EngineArg::Tectonic => Engine::Tectonic,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Section {
+    Personal,
+    Education,
+    Experience,
+    Projects,
+    Skills,
+    Profiles,
+}
+
+pub fn run(cli: Cli) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    match cli.command.unwrap_or(Command::Tui { keep_history: false }) {
+        Command::Tui { keep_history } => {
+            tui::run_app(&cli.data, keep_history)?;
+        }
+        Command::Export { all: true, output, pdf, engine, force, .. } => {
+            let data_manager = DataManager::new(&cli.data)?;
+            let engine = if pdf { Some(pdf_engine(engine)?) } else { None };
+            let batch = BatchExporter::new(output.unwrap_or_else(|| PathBuf::from("output")))
+                .with_pdf(engine)
+                .with_force(force);
+            let report = batch.run(&data_manager.resume);
+            for target in &report.targets {
+                println!("{}", target);
+                if let Outcome::Written { warnings, .. } = &target.outcome {
+                    for warning in warnings {
+                        eprintln!("warning: {}", warning);
+                    }
+                }
+            }
+            if let Some(e) = &report.manifest_error {
+                eprintln!("warning: could not record export hashes: {}", e);
+            }
+            println!("{}: {}", batch.root().display(), report.summary());
+            if report.failed() > 0 {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Export { format, template, output, width, pdf, engine, profile, .. } => {
+            let data_manager = DataManager::new(&cli.data)?;
+            let profile = profile.map(|name| data_manager.profile(&name).cloned()).transpose()?;
+            let output = output.or_else(|| profile.as_ref().map(|p| Format::from(format).default_output_for(Some(&p.name))));
+            let mut exporter = exporter_for(format.into(), template, output).with_profile(profile);
+            if Format::from(format) == Format::Text {
+                exporter = exporter.with_renderer(Box::new(TextRenderer::new(width)));
+            }
+            if pdf {
+                exporter = exporter.with_pdf(Some(pdf_engine(engine)?));
+            }
+            let result = exporter.export(&data_manager.resume)?;
+            if result.destination != Destination::Stdout {
+                println!("Generated {} ({} bytes)", result.destination, result.bytes);
+            }
+            for warning in &result.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            if let Some(pdf) = result.pdf {
+                println!("Compiled {} with {} (log: {})", pdf.pdf.display(), pdf.engine, pdf.log.display());
+            }
+        }
+        Command::Validate { format, template, strict, profile } => {
+            let data_manager = DataManager::new(&cli.data)?;
+            let issues = data_manager.validate();
+            for issue in &issues {
+                println!("{}", issue);
+            }
+            let errors = issues.iter().filter(|i| i.severity == Severity::Error).count();
+            let warnings = issues.len() - errors;
+            println!("{}: {} error(s), {} warning(s)", cli.data, errors, warnings);
+            let mut failed = errors > 0 || (strict && warnings > 0);
+
+            let exporter = exporter_for(format.into(), template, None)
+                .with_profile(profile.map(|name| data_manager.profile(&name).cloned()).transpose()?);
+            match exporter.render(&data_manager.resume) {
+                Ok(_) => println!("{}: template OK", exporter.template().describe()),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    failed = true;
+                }
+            }
+            if failed {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Template { format, output, force } => {
+            let format = Format::from(format);
+            if !format.has_template() {
+                eprintln!("{} output does not use a template", format.title());
+                return Ok(ExitCode::FAILURE);
+            }
+            let path = output.unwrap_or_else(|| format.default_template());
+            if path.exists() && !force {
+                eprintln!("{} already exists; pass --force to overwrite", path.display());
+                return Ok(ExitCode::FAILURE);
+            }
+            export::write_builtin_template(format, &path)?;
+            println!("Wrote built-in {} template to {}", format, path.display());
+        }
+        Command::Import { input, force } => {
+            if PathBuf::from(&cli.data).exists() && !force {
+                eprintln!("{} already exists; pass --force to overwrite", cli.data);
+                return Ok(ExitCode::FAILURE);
+            }
+            let (mut data_manager, report) = DataManager::import_json_resume(&input.to_string_lossy(), &cli.data)?;
+            if let Some(parent) = PathBuf::from(&cli.data).parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            data_manager.save()?;
+            println!("Imported {} into {}", input.display(), cli.data);
+            for path in &report.unmapped {
+                eprintln!("warning: {} was not imported", path);
+            }
+        }
+        Command::Convert { output, force } => {
+            if output.exists() && !force {
+                eprintln!("{} already exists; pass --force to overwrite", output.display());
+                return Ok(ExitCode::FAILURE);
+            }
+            let mut data_manager = DataManager::new(&cli.data)?;
+            let from = data_manager.format();
+            // Creates the output's directory; the input file is left as it is.
+            data_manager.save_as(&output.to_string_lossy())?;
+            println!("Converted {} ({}) to {} ({})", cli.data, from, output.display(), data_manager.format());
+        }
+        Command::List { section } => {
+            let data_manager = DataManager::new(&cli.data)?;
+            list_section(&data_manager, section);
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn pdf_engine(engine: Option<EngineArg>) -> Result<Engine, &'static str> {
+    engine
+        .map(Engine::from)
+        .or_else(Engine::detect)
+        .ok_or("no LaTeX engine found on PATH; install one or pass --engine")
+}
+
+fn exporter_for(format: Format, template: Option<PathBuf>, output: Option<PathBuf>) -> Exporter {
+    let mut exporter = Exporter::new(format);
+    if let Some(template) = template {
+        exporter = exporter.with_template(TemplateSource::File(template));
+    }
+    match output {
+        Some(path) if path.as_os_str() == "-" => exporter.with_destination(Destination::Stdout),
+        Some(path) => exporter.with_destination(Destination::File(path)),
+        None => exporter,
+    }
+}
+
+fn list_section(data_manager: &DataManager, section: Section) {
+    let resume = &data_manager.resume;
+    match section {
+        Section::Personal => {
+            println!("Name: {}", resume.name);
+            println!("Email: {}", resume.contact.email);
+            println!("Phone: {}", resume.contact.phone);
+            println!("Location: {}", resume.contact.location);
+            if let Some(website) = &resume.website {
+                println!("Website: {}", website);
+            }
+            for profile in &resume.contact.profiles {
+                println!("{}: {}", profile.label, profile.url);
+            }
+        }
+        Section::Education => {
+            for (i, education) in resume.education.iter().enumerate() {
+                println!("{}. {} - {} ({})", i + 1, education.name, education.degree, education.dates);
+                print_points(&education.points);
+            }
+        }
+        Section::Experience => {
+            for (i, experience) in resume.experience.iter().enumerate() {
+                println!("{}. {} at {} ({})", i + 1, experience.position, experience.company, experience.dates);
+                print_points(&experience.points);
+            }
+        }
+        Section::Projects => {
+            for (i, project) in resume.projects.iter().enumerate() {
+                match &project.link {
+                    Some(link) => println!("{}. {} <{}>", i + 1, project.name, link),
+                    None => println!("{}. {}", i + 1, project.name),
+                }
+                print_points(&project.points);
+            }
+        }
+        Section::Skills => {
+            for (i, category) in resume.skills.iter().enumerate() {
+                let items: Vec<String> = category
+                    .items
+                    .iter()
+                    .map(|skill| match &skill.proficiency {
+                        Some(level) => format!("{} ({})", skill.name, level),
+                        None => skill.name.clone(),
+                    })
+                    .collect();
+                println!("{}. {}: {}", i + 1, category.name, items.join(", "));
+            }
+        }
+        Section::Profiles => {
+            for (i, profile) in resume.profiles.iter().enumerate() {
+                let sections: Vec<&str> = profile.sections().iter().map(|s| s.name()).collect();
+                println!("{}. {}: {}", i + 1, profile.name, sections.join(", "));
+                if !profile.tags.is_empty() {
+                    println!("   tags: {}", profile.tags.join(", "));
+                }
+            }
+        }
+    }
+}
+
+fn print_points(points: &[Point]) {
+    for point in points {
+        if point.tags.is_empty() {
+            println!("   - {}", point.text);
+        } else {
+            println!("   - {} [{}]", point.text, point.tags.join(", "));
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Crash-safe writes and rotating, timestamped backups of the data file.
+//!
+//! Backups sit next to the data file as `<file>.<yyyymmdd-hhmmss-mmm>.bak`,
+//! stamped in UTC when they were taken.
+
+use crate::data::error::DataError;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How many backups `DataManager::save` keeps by default.
+pub const DEFAULT_KEEP: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    stamp: String,
+}
+
+impl Backup {
+    /// When the backup was taken, e.g. "2026-10-17 15:30:00 UTC".
+    pub fn label(&self) -> String {
+        let s = &self.stamp;
+        format!(
+            "{}-{}-{} {}:{}:{} UTC",
+            &s[0..4],
+            &s[4..6],
+            &s[6..8],
+            &s[9..11],
+            &s[11..13],
+            &s[13..15]
+        )
+    }
+}
+
+/// Writes `contents` to a temp file in the same directory, then renames it
+/// over `path`, so `path` is never left half-written.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<(), DataError> {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("data");
+    let temp = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result.map_err(DataError::io(path))
+}
+
+/// Copies the current `file_path` into a new backup and deletes all but
+/// the newest `keep`. Does nothing if the file does not exist yet.
+pub fn create(file_path: &Path, keep: usize) -> Result<Option<Backup>, DataError> {
+    if keep == 0 || !file_path.exists() {
+        return Ok(None);
+    }
+
+    let stamp = timestamp(SystemTime::now());
+    let backup = Backup {
+        path: backup_path(file_path, &stamp),
+        stamp,
+    };
+    fs::copy(file_path, &backup.path).map_err(DataError::io(&backup.path))?;
+
+    for old in list(file_path).into_iter().skip(keep) {
+        fs::remove_file(&old.path).map_err(DataError::io(&old.path))?;
+    }
+    Ok(Some(backup))
+}
+
+/// Backups of `file_path`, newest first.
+pub fn list(file_path: &Path) -> Vec<Backup> {
+    let Some(file_name) = file_path.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    let dir = match file_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let prefix = format!("{}.", file_name);
+    let mut backups: Vec<Backup> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let stamp = name.strip_prefix(&prefix)?.strip_suffix(".bak")?;
+            is_stamp(stamp).then(|| Backup {
+                path: file_path.with_file_name(&name),
+                stamp: stamp.to_string(),
+            })
+        })
+        .collect();
+
+    backups.sort_by(|a, b| b.stamp.cmp(&a.stamp));
+    backups
+}
+
+fn backup_path(file_path: &Path, stamp: &str) -> PathBuf {
+    let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("data");
+    file_path.with_file_name(format!("{}.{}.bak", file_name, stamp))
+}
+
+fn is_stamp(stamp: &str) -> bool {
+    stamp.len() == 19
+        && stamp.char_indices().all(|(i, c)| match i {
+            8 | 15 => c == '-',
+            _ => c.is_ascii_digit(),
+        })
+}
+
+/// Formats `time` as `yyyymmdd-hhmmss-mmm` in UTC.
+fn timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs() as i64;
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let of_day = secs.rem_euclid(86_400);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}-{:03}",
+        year,
+        month,
+        day,
+        of_day / 3600,
+        of_day % 3600 / 60,
+        of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+// Howard Hinnant's days-to-civil algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("resume-utd-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn at(secs: u64, millis: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(secs * 1000 + millis)
+    }
+
+    #[test]
+    fn timestamps_are_utc_calendar_times() {
+        assert_eq!(timestamp(at(0, 0)), "19700101-000000-000");
+        assert_eq!(timestamp(at(951_782_400, 0)), "20000229-000000-000");
+        assert_eq!(timestamp(at(1_700_000_000, 123)), "20231114-221320-123");
+        assert_eq!(timestamp(at(4_102_444_799, 999)), "20991231-235959-999");
+    }
+
+    #[test]
+    fn recognises_only_full_stamps() {
+        assert!(is_stamp("20231114-221320-123"));
+        for stamp in ["20231114-221320", "20231114_221320-123", "2023111a-221320-123", "20231114-221320-1234", ""] {
+            assert!(!is_stamp(stamp), "{}", stamp);
+        }
+    }
+
+    #[test]
+    fn labels_read_as_dates() {
+        let backup = Backup {
+            path: PathBuf::from("resume.json.20231114-221320-123.bak"),
+            stamp: "20231114-221320-123".to_string(),
+        };
+        assert_eq!(backup.label(), "2023-11-14 22:13:20 UTC");
+    }
+
+    #[test]
+    fn keeps_only_the_newest_backups() {
+        let dir = temp_dir("backups");
+        let file = dir.join("resume.json");
+        fs::write(&file, "current").unwrap();
+        for stamp in ["20200101-000000-000", "20210101-000000-000", "20220101-000000-000"] {
+            fs::write(backup_path(&file, stamp), stamp).unwrap();
+        }
+        // Not backups: another file's, and a stray name.
+        fs::write(dir.join("other.json.20200101-000000-000.bak"), "").unwrap();
+        fs::write(dir.join("resume.json.old.bak"), "").unwrap();
+
+        let created = create(&file, 3).unwrap().unwrap();
+        let backups = list(&file);
+        let stamps: Vec<&str> = backups.iter().map(|b| b.stamp.as_str()).collect();
+        assert_eq!(backups[0], created);
+        assert_eq!(stamps[1..], ["20220101-000000-000", "20210101-000000-000"]);
+        assert_eq!(fs::read_to_string(&created.path).unwrap(), "current");
+        assert!(!backup_path(&file, "20200101-000000-000").exists());
+        assert!(dir.join("other.json.20200101-000000-000.bak").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn no_backup_without_a_file_or_when_disabled() {
+        let dir = temp_dir("no-backups");
+        let file = dir.join("resume.json");
+        assert_eq!(create(&file, 3).unwrap(), None);
+        fs::write(&file, "current").unwrap();
+        assert_eq!(create(&file, 0).unwrap(), None);
+        assert!(list(&file).is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn atomic_writes_replace_the_file_and_leave_no_temp_behind() {
+        let dir = temp_dir("atomic");
+        let file = dir.join("resume.json");
+        write_atomic(&file, "first").unwrap();
+        write_atomic(&file, "second").unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "second");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        let missing = dir.join("missing").join("resume.json");
+        assert!(matches!(write_atomic(&missing, "lost"), Err(DataError::Io { .. })));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,344 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const MONTH_NAMES: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+/// One end of a date range: a bare year, a year and month, or "present".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateBound {
+    Year(i32),
+    YearMonth(i32, u32),
+    Present,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DateStyle {
+    /// "Jan 2022 – Present"
+    #[default]
+    Month,
+    /// "2022–2024"
+    Year,
+    /// "01/2022 – 05/2024"
+    Numeric,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct DateRange {
+    pub start: Option<DateBound>,
+    pub end: Option<DateBound>,
+    /// Original text kept when an old `years` string could not be parsed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl DateBound {
+    pub fn parse(input: &str) -> Option<Self> {
+        let lower = input
+            .trim()
+            .trim_matches(|c: char| c == '.' || c == ',')
+            .to_lowercase();
+        let value = lower.strip_prefix("expected").unwrap_or(&lower).trim();
+
+        if matches!(value, "present" | "current" | "now" | "ongoing") {
+            return Some(DateBound::Present);
+        }
+        if let Some(year) = parse_year(value) {
+            return Some(DateBound::Year(year));
+        }
+
+        // 2022-01, 2022/01, 01/2022, 01-2022
+        if let Some((a, b)) = value.split_once(['-', '/']) {
+            if let (Some(year), Some(month)) = (parse_year(a), parse_month_number(b)) {
+                return Some(DateBound::YearMonth(year, month));
+            }
+            if let (Some(month), Some(year)) = (parse_month_number(a), parse_year(b)) {
+                return Some(DateBound::YearMonth(year, month));
+            }
+        }
+
+        // Jan 2022, Sept. 2022, August 2022
+        if let Some((a, b)) = value.split_once(char::is_whitespace)
+            && let (Some(month), Some(year)) = (parse_month_name(a), parse_year(b.trim()))
+        {
+            return Some(DateBound::YearMonth(year, month));
+        }
+
+        None
+    }
+
+    /// The first and last month the bound covers; a bare year covers all
+    /// twelve.
+    fn months(&self) -> ((i32, u32), (i32, u32)) {
+        match self {
+            DateBound::Year(year) => ((*year, 1), (*year, 12)),
+            DateBound::YearMonth(year, month) => ((*year, *month), (*year, *month)),
+            DateBound::Present => ((i32::MAX, 1), (i32::MAX, 12)),
+        }
+    }
+
+    pub fn format(&self, style: DateStyle) -> String {
+        match (self, style) {
+            (DateBound::Present, _) => "Present".to_string(),
+            (DateBound::Year(year), _) | (DateBound::YearMonth(year, _), DateStyle::Year) => {
+                year.to_string()
+            }
+            (DateBound::YearMonth(year, month), DateStyle::Month) => {
+                format!("{} {}", MONTHS[(*month - 1) as usize], year)
+            }
+            (DateBound::YearMonth(year, month), DateStyle::Numeric) => {
+                format!("{:02}/{}", month, year)
+            }
+        }
+    }
+}
+
+/// Bounds sort by the first month they cover, then the last, so a bare
+/// year sorts after its January and before its other months. This uses the
+/// same periods as `DateRange::is_reversed`, which asks whether they
+/// overlap instead: "2022-05 – 2022" has its start sorting after its end
+/// but is not reversed.
+impl Ord for DateBound {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.months().cmp(&other.months())
+    }
+}
+
+impl PartialOrd for DateBound {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for DateBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateBound::Year(year) => write!(f, "{}", year),
+            DateBound::YearMonth(year, month) => write!(f, "{}-{:02}", year, month),
+            DateBound::Present => write!(f, "present"),
+        }
+    }
+}
+
+impl Serialize for DateBound {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for DateBound {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        DateBound::parse(&value)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid date '{}'", value)))
+    }
+}
+
+impl DateStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "month" | "long" => Some(DateStyle::Month),
+            "year" | "short" => Some(DateStyle::Year),
+            "numeric" => Some(DateStyle::Numeric),
+            _ => None,
+        }
+    }
+
+    pub fn default_separator(&self) -> &'static str {
+        match self {
+            DateStyle::Year => "–",
+            DateStyle::Month | DateStyle::Numeric => " – ",
+        }
+    }
+}
+
+impl DateRange {
+    /// Leniently parses strings like "2020 - 2024", "Jan 2022 – Present" or
+    /// "05/2021". Anything unrecognised is kept verbatim in `text`.
+    pub fn parse(input: &str) -> Self {
+        let input = input.trim();
+        if input.is_empty() {
+            return DateRange::default();
+        }
+
+        let parts = ["--", "–", "—", " to ", " - "]
+            .iter()
+            .find_map(|sep| input.split_once(sep))
+            .or_else(|| {
+                // "2020-2024" with no spaces, which must not be mistaken for "2020-01".
+                input
+                    .split_once('-')
+                    .filter(|(a, b)| parse_year(a.trim()).is_some() && parse_year(b.trim()).is_some())
+            });
+
+        let parsed = match parts {
+            Some((start, end)) => DateBound::parse(start)
+                .zip(DateBound::parse(end))
+                .map(|(start, end)| (Some(start), Some(end))),
+            None => DateBound::parse(input).map(|start| (Some(start), None)),
+        };
+
+        match parsed {
+            Some((start, end)) => DateRange { start, end, text: None },
+            None => DateRange {
+                start: None,
+                end: None,
+                text: Some(input.to_string()),
+            },
+        }
+    }
+
+    /// Whether the range ends before it starts. A bare year stands for the
+    /// whole year, so "2022-05 – 2022" and "2022 – 2022-03" are both fine.
+    pub fn is_reversed(&self) -> bool {
+        match (&self.start, &self.end) {
+            (Some(start), Some(end)) => start.months().0 > end.months().1,
+            _ => false,
+        }
+    }
+
+    pub fn format(&self, style: DateStyle, separator: &str) -> String {
+        match (&self.start, &self.end, &self.text) {
+            (Some(start), Some(end), _) => {
+                format!("{}{}{}", start.format(style), separator, end.format(style))
+            }
+            (Some(date), None, _) | (None, Some(date), _) => date.format(style),
+            (None, None, Some(text)) => text.clone(),
+            (None, None, None) => String::new(),
+        }
+    }
+}
+
+impl fmt::Display for DateRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = DateStyle::Month;
+        write!(f, "{}", self.format(style, style.default_separator()))
+    }
+}
+
+fn parse_year(value: &str) -> Option<i32> {
+    if value.len() == 4 && value.chars().all(|c| c.is_ascii_digit()) {
+        value.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn parse_month_number(value: &str) -> Option<u32> {
+    if value.is_empty() || value.len() > 2 || !value.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok().filter(|m| (1..=12).contains(m))
+}
+
+/// A full month name or its abbreviation ("Sep", or "Sept" as commonly
+/// written), with or without a trailing period.
+fn parse_month_name(value: &str) -> Option<u32> {
+    let value = value.trim_end_matches('.');
+    let index = match value {
+        "sept" => 8,
+        _ => MONTH_NAMES
+            .iter()
+            .zip(MONTHS)
+            .position(|(name, short)| value == *name || value == short.to_lowercase())?,
+    };
+    Some(index as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn month_names_must_match_whole_words() {
+        assert_eq!(DateBound::parse("June 2022"), Some(DateBound::YearMonth(2022, 6)));
+        assert_eq!(DateBound::parse("Jun 2022"), Some(DateBound::YearMonth(2022, 6)));
+        assert_eq!(DateBound::parse("Sept. 2022"), Some(DateBound::YearMonth(2022, 9)));
+        assert_eq!(DateBound::parse("sep 2022"), Some(DateBound::YearMonth(2022, 9)));
+        assert_eq!(DateBound::parse("junk 2022"), None);
+        assert_eq!(DateBound::parse("marble 2022"), None);
+        assert_eq!(DateBound::parse("Janu 2022"), None);
+    }
+
+    fn range(start: Option<DateBound>, end: Option<DateBound>) -> DateRange {
+        DateRange { start, end, text: None }
+    }
+
+    #[test]
+    fn parses_common_range_forms() {
+        use DateBound::*;
+        assert_eq!(DateRange::parse("2020 - 2024"), range(Some(Year(2020)), Some(Year(2024))));
+        assert_eq!(DateRange::parse("2020-2024"), range(Some(Year(2020)), Some(Year(2024))));
+        assert_eq!(DateRange::parse("Jan 2022 – Present"), range(Some(YearMonth(2022, 1)), Some(Present)));
+        assert_eq!(DateRange::parse("2021-08 to 05/2025"), range(Some(YearMonth(2021, 8)), Some(YearMonth(2025, 5))));
+        assert_eq!(DateRange::parse("Expected May 2025"), range(Some(YearMonth(2025, 5)), None));
+        assert_eq!(DateRange::parse("2022-01"), range(Some(YearMonth(2022, 1)), None));
+        assert_eq!(DateRange::parse("  "), DateRange::default());
+    }
+
+    #[test]
+    fn keeps_unrecognised_text() {
+        let parsed = DateRange::parse("Summers 2019-2021");
+        assert_eq!(parsed.start, None);
+        assert_eq!(parsed.text.as_deref(), Some("Summers 2019-2021"));
+        assert_eq!(parsed.to_string(), "Summers 2019-2021");
+
+        // Both ends must parse, or the whole range is kept as text.
+        assert_eq!(DateRange::parse("2019 - someday").text.as_deref(), Some("2019 - someday"));
+        assert_eq!(DateBound::parse("2022-13"), None);
+    }
+
+    #[test]
+    fn sorts_by_the_months_covered_and_present_last() {
+        use DateBound::*;
+        let mut bounds = vec![Present, YearMonth(2022, 3), Year(2023), Year(2022), YearMonth(2022, 1), YearMonth(2021, 12)];
+        bounds.sort();
+        assert_eq!(bounds, [YearMonth(2021, 12), YearMonth(2022, 1), Year(2022), YearMonth(2022, 3), Year(2023), Present]);
+    }
+
+    #[test]
+    fn ordering_agrees_with_equality() {
+        use DateBound::*;
+        let bounds = [Year(2022), YearMonth(2022, 1), YearMonth(2022, 12), Present];
+        for a in bounds {
+            for b in bounds {
+                assert_eq!(a.cmp(&b) == Ordering::Equal, a == b, "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn reversed_ranges_start_after_they_end() {
+        let reversed = |input: &str| DateRange::parse(input).is_reversed();
+        assert!(reversed("2023 - 2022"));
+        assert!(reversed("Jun 2022 - May 2022"));
+        assert!(!reversed("2022-05 - 2022"));
+        assert!(!reversed("2022 - 2022-03"));
+        assert!(!reversed("2022 - Present"));
+        assert!(!reversed("2022"));
+    }
+
+    #[test]
+    fn formats_in_each_style() {
+        let dates = DateRange::parse("Jan 2022 - May 2024");
+        assert_eq!(dates.to_string(), "Jan 2022 – May 2024");
+        assert_eq!(dates.format(DateStyle::Year, DateStyle::Year.default_separator()), "2022–2024");
+        assert_eq!(dates.format(DateStyle::Numeric, " - "), "01/2022 - 05/2024");
+    }
+
+    #[test]
+    fn serializes_bounds_as_strings() {
+        let dates = DateRange::parse("Aug 2021 - Present");
+        let json = serde_json::to_string(&dates).unwrap();
+        assert_eq!(json, r#"{"start":"2021-08","end":"present"}"#);
+        assert_eq!(serde_json::from_str::<DateRange>(&json).unwrap(), dates);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::data::format::DataFormat;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum DataError {
+    Io { path: PathBuf, error: io::Error },
+    /// The file exists but is not valid for its format. `line` and `column`
+    /// are 1-based when the parser reports them.
+    Parse {
+        path: PathBuf,
+        format: DataFormat,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+    Validation(String),
+    IndexOutOfRange { section: &'static str, index: usize, len: usize },
+}
+
+impl DataError {
+    pub(crate) fn io(path: &Path) -> impl FnOnce(io::Error) -> DataError {
+        let path = path.to_path_buf();
+        move |error| DataError::Io { path, error }
+    }
+
+    /// Whether the file simply does not exist yet, as on first run.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, DataError::Io { error, .. } if error.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            DataError::Parse { path, format, line, column, message } => {
+                write!(f, "{}", path.display())?;
+                if let Some(line) = line {
+                    write!(f, ":{}", line)?;
+                    if let Some(column) = column {
+                        write!(f, ":{}", column)?;
+                    }
+                }
+                write!(f, ": invalid {}: {}", format, message)
+            }
+            DataError::Validation(message) => write!(f, "{}", message),
+            DataError::IndexOutOfRange { section, index, len } => {
+                write!(f, "{} index {} out of range ({} entries)", section, index, len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DataError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
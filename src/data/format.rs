@@ -0,0 +1,183 @@
+use crate::data::error::DataError;
+use crate::data::manager::Resume;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// On-disk encodings for resume data, chosen by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl DataFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DataFormat::Json => "JSON",
+            DataFormat::Yaml => "YAML",
+            DataFormat::Toml => "TOML",
+        }
+    }
+
+    /// `.yaml`/`.yml` and `.toml` files get those formats; anything else is JSON.
+    pub fn from_path(path: &Path) -> DataFormat {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        match extension.as_deref() {
+            Some("yaml" | "yml") => DataFormat::Yaml,
+            Some("toml") => DataFormat::Toml,
+            _ => DataFormat::Json,
+        }
+    }
+
+    /// Parses `text` as a `Resume`. The path in a returned `Parse` error is
+    /// left empty for the caller to fill in.
+    pub fn parse(&self, text: &str) -> Result<Resume, DataError> {
+        match self {
+            DataFormat::Json => serde_json::from_str(text).map_err(|e| self.json_error(e)),
+            DataFormat::Yaml => serde_yaml_ng::from_str(text).map_err(|e| self.yaml_error(e)),
+            DataFormat::Toml => toml::from_str(text).map_err(|e| self.toml_error(e, text)),
+        }
+    }
+
+    /// Parses `text` without the `Resume` shape, so a file whose syntax is
+    /// fine can still be opened section by section.
+    pub fn parse_value(&self, text: &str) -> Result<serde_json::Value, DataError> {
+        match self {
+            DataFormat::Json => serde_json::from_str(text).map_err(|e| self.json_error(e)),
+            DataFormat::Yaml => serde_yaml_ng::from_str(text).map_err(|e| self.yaml_error(e)),
+            DataFormat::Toml => toml::from_str(text).map_err(|e| self.toml_error(e, text)),
+        }
+    }
+
+    fn json_error(&self, error: serde_json::Error) -> DataError {
+        self.parse_error(Some(error.line()), Some(error.column()), error.to_string())
+    }
+
+    fn yaml_error(&self, error: serde_yaml_ng::Error) -> DataError {
+        let location = error.location();
+        self.parse_error(
+            location.as_ref().map(|l| l.line()),
+            location.as_ref().map(|l| l.column()),
+            error.to_string(),
+        )
+    }
+
+    fn toml_error(&self, error: toml::de::Error, text: &str) -> DataError {
+        // toml reports a byte span rather than a line and column.
+        let position = error.span().map(|span| {
+            let before = &text[..span.start.min(text.len())];
+            let line = before.matches('\n').count() + 1;
+            let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+            (line, column)
+        });
+        self.parse_error(
+            position.map(|(line, _)| line),
+            position.map(|(_, column)| column),
+            error.message().to_string(),
+        )
+    }
+
+    fn parse_error(&self, line: Option<usize>, column: Option<usize>, message: String) -> DataError {
+        // serde_json and serde_yaml_ng append the position to the message.
+        let message = match message.rfind(" at line ") {
+            Some(index) if line.is_some() => message[..index].to_string(),
+            _ => message,
+        };
+        DataError::Parse {
+            path: PathBuf::new(),
+            format: *self,
+            line,
+            column,
+            message,
+        }
+    }
+
+    pub fn serialize(&self, resume: &Resume) -> io::Result<String> {
+        match self {
+            DataFormat::Json => Ok(serde_json::to_string_pretty(resume)?),
+            DataFormat::Yaml => serde_yaml_ng::to_string(resume).map_err(io::Error::other),
+            DataFormat::Toml => toml::to_string_pretty(resume).map_err(io::Error::other),
+        }
+    }
+}
+
+impl fmt::Display for DataFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A resume that uses every optional field and each shape of bullet
+    /// point and date range.
+    fn populated() -> Resume {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": 2,
+            "name": "Ada Lovelace",
+            "contact": { "email": "ada@example.com", "phone": "+44 20 7946 0000", "location": "London",
+                         "profiles": [{ "label": "GitHub", "url": "https://github.com/ada" }] },
+            "website": "https://ada.dev",
+            "education": [{ "name": "UT Dallas", "degree": "BS", "dates": { "start": "2018", "end": "2022-05" },
+                            "points": ["Dean's list"], "tags": ["academic"] }],
+            "projects": [
+                { "name": "resume-utd", "link": "https://example.com/resume", "points": [] },
+                { "name": "Notes", "link": null, "points": [{ "text": "Wrote a parser", "tags": ["rust"] }] }
+            ],
+            "experience": [
+                { "company": "Acme", "position": "Engineer", "dates": { "start": "2022-06", "end": "present" },
+                  "points": ["Shipped things", { "text": "Led the migration", "tags": ["backend", "lead"] }] },
+                { "company": "Initech", "position": "Intern", "dates": { "start": "2021" }, "points": [] },
+                { "company": "Globex", "position": "Tutor", "dates": { "text": "Summers, mostly" }, "points": [] }
+            ],
+            "skills": [{ "name": "Languages", "items": [{ "name": "Rust", "proficiency": "Advanced" },
+                                                       { "name": "C", "proficiency": null }], "tags": ["core"] }],
+            "profiles": [{ "name": "backend", "sections": ["experience", "skills"], "tags": ["backend"] }]
+        }))
+        .unwrap()
+    }
+
+    fn round_trip(format: DataFormat) {
+        let resume = populated();
+        let text = format.serialize(&resume).unwrap();
+        assert_eq!(format.parse(&text).unwrap(), resume, "{} did not round-trip:\n{}", format, text);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        round_trip(DataFormat::Json);
+    }
+
+    #[test]
+    fn round_trips_through_yaml() {
+        round_trip(DataFormat::Yaml);
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        round_trip(DataFormat::Toml);
+    }
+
+    #[test]
+    fn picks_the_format_from_the_extension() {
+        assert_eq!(DataFormat::from_path(Path::new("resume.YML")), DataFormat::Yaml);
+        assert_eq!(DataFormat::from_path(Path::new("data/resume.toml")), DataFormat::Toml);
+        assert_eq!(DataFormat::from_path(Path::new("resume")), DataFormat::Json);
+    }
+
+    #[test]
+    fn parse_errors_carry_the_position() {
+        let error = DataFormat::Toml.parse("name = \"Ada\"\ncontact = 3\n").unwrap_err();
+        match error {
+            DataError::Parse { line, column, .. } => assert_eq!((line, column), (Some(2), Some(11))),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
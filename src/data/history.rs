@@ -0,0 +1,354 @@
+//! Undo/redo for edits made through `DataManager`.
+//!
+//! Each edit is kept as the top-level resume fields it changed (`education`,
+//! `contact`, ...), before and after, so a typo fix in one entry costs that
+//! one section rather than a copy of the whole resume. The undo list is
+//! bounded by both entry count and size; the oldest edits are dropped first.
+//!
+//! When persistence is on, the history sits next to the data file as
+//! `<file>.history.json`, along with a copy of the data it was saved with.
+//! It is only reused if that copy still matches the data file, so a file
+//! edited by hand simply starts a fresh history.
+
+use crate::data::backup;
+use crate::data::error::DataError;
+use crate::data::manager::Resume;
+use crate::data::migrate;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How many edits can be undone by default.
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// Upper bound on the serialized size of all kept edits, in bytes.
+pub const MAX_BYTES: usize = 4 * 1024 * 1024;
+
+/// One recorded edit. A field missing from the resume is stored as `null`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Change {
+    pub label: String,
+    before: Map<String, Value>,
+    after: Map<String, Value>,
+    #[serde(skip)]
+    size: usize,
+}
+
+#[derive(Debug)]
+pub struct History {
+    undo: VecDeque<Change>,
+    redo: Vec<Change>,
+    limit: usize,
+    /// Where the history is kept between sessions, if anywhere.
+    path: Option<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Stored {
+    schema_version: u32,
+    /// The resume the history ends at.
+    data: Value,
+    undo: VecDeque<Change>,
+    redo: Vec<Change>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_LIMIT)
+    }
+}
+
+impl Change {
+    fn new(label: String, before: &Value, after: &Value) -> Option<Self> {
+        let empty = Map::new();
+        let before = before.as_object().unwrap_or(&empty);
+        let after = after.as_object().unwrap_or(&empty);
+
+        let mut change = Change {
+            label,
+            before: Map::new(),
+            after: Map::new(),
+            size: 0,
+        };
+        for key in before.keys().chain(after.keys()) {
+            let old = before.get(key).unwrap_or(&Value::Null);
+            let new = after.get(key).unwrap_or(&Value::Null);
+            if old != new && !change.before.contains_key(key) {
+                change.before.insert(key.clone(), old.clone());
+                change.after.insert(key.clone(), new.clone());
+            }
+        }
+        if change.before.is_empty() {
+            return None;
+        }
+        change.measure();
+        Some(change)
+    }
+
+    fn measure(&mut self) {
+        self.size = serde_json::to_string(&self.before).map_or(0, |s| s.len())
+            + serde_json::to_string(&self.after).map_or(0, |s| s.len());
+    }
+}
+
+impl History {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit,
+            path: None,
+        }
+    }
+
+    /// The history file kept for `data_file`.
+    pub fn path_for(data_file: &Path) -> PathBuf {
+        let mut name = data_file.file_name().unwrap_or_default().to_os_string();
+        name.push(".history.json");
+        data_file.with_file_name(name)
+    }
+
+    /// Keeps the history in `path` from now on, picking up what an earlier
+    /// session left there if it still applies to `resume`. Returns whether
+    /// an earlier history was picked up.
+    pub fn persist_to(&mut self, path: PathBuf, resume: &Resume) -> bool {
+        let current = serde_json::to_value(resume).unwrap_or_default();
+        let stored = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<Stored>(&text).ok())
+            .filter(|stored| stored.schema_version == migrate::CURRENT_VERSION && stored.data == current);
+        self.path = Some(path);
+
+        let Some(stored) = stored else {
+            return false;
+        };
+        self.undo = stored.undo;
+        self.redo = stored.redo;
+        self.undo.iter_mut().chain(self.redo.iter_mut()).for_each(Change::measure);
+        self.trim();
+        true
+    }
+
+    /// Moves a persisted history to `path`, as when the data is saved under
+    /// a new name. An unpersisted history stays in memory.
+    pub fn relocate(&mut self, path: PathBuf) {
+        if self.path.is_some() {
+            self.path = Some(path);
+        }
+    }
+
+    /// Writes the history to its file, if it has one, marked as ending at
+    /// `resume`.
+    pub fn save(&self, resume: &Resume) -> Result<(), DataError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let invalid = |e: serde_json::Error| DataError::Validation(e.to_string());
+        let stored = Stored {
+            schema_version: migrate::CURRENT_VERSION,
+            data: serde_json::to_value(resume).map_err(invalid)?,
+            undo: self.undo.clone(),
+            redo: self.redo.clone(),
+        };
+        let text = serde_json::to_string(&stored).map_err(invalid)?;
+        backup::write_atomic(path, &text)
+    }
+
+    /// Records the edit that turned `before` into `after`, if it changed
+    /// anything. Recording clears the redo list.
+    pub fn record(&mut self, label: impl Into<String>, before: &Resume, after: &Resume) {
+        let (Ok(old), Ok(new)) = (serde_json::to_value(before), serde_json::to_value(after)) else {
+            return;
+        };
+        if let Some(change) = Change::new(label.into(), &old, &new) {
+            self.undo.push_back(change);
+            self.redo.clear();
+            self.trim();
+        }
+    }
+
+    /// Reverts the latest edit in `resume` and returns its label.
+    pub fn undo(&mut self, resume: &mut Resume) -> Result<Option<String>, DataError> {
+        let Some(change) = self.undo.pop_back() else {
+            return Ok(None);
+        };
+        if let Err(e) = apply(resume, &change.before) {
+            self.undo.push_back(change);
+            return Err(e);
+        }
+        let label = change.label.clone();
+        self.redo.push(change);
+        Ok(Some(label))
+    }
+
+    /// Re-applies the latest undone edit in `resume` and returns its label.
+    pub fn redo(&mut self, resume: &mut Resume) -> Result<Option<String>, DataError> {
+        let Some(change) = self.redo.pop() else {
+            return Ok(None);
+        };
+        if let Err(e) = apply(resume, &change.after) {
+            self.redo.push(change);
+            return Err(e);
+        }
+        let label = change.label.clone();
+        self.undo.push_back(change);
+        Ok(Some(label))
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Drops the oldest edits until the history is within its limits.
+    fn trim(&mut self) {
+        let mut size: usize = self.undo.iter().chain(&self.redo).map(|c| c.size).sum();
+        while self.undo.len() > self.limit || (size > MAX_BYTES && !self.undo.is_empty()) {
+            if let Some(change) = self.undo.pop_front() {
+                size -= change.size;
+            }
+        }
+    }
+}
+
+/// Sets `fields` in `resume`, removing those stored as `null`.
+fn apply(resume: &mut Resume, fields: &Map<String, Value>) -> Result<(), DataError> {
+    let invalid = |e: serde_json::Error| DataError::Validation(format!("could not restore the edit: {}", e));
+    let mut value = serde_json::to_value(&*resume).map_err(invalid)?;
+    if let Some(object) = value.as_object_mut() {
+        for (key, field) in fields {
+            if field.is_null() {
+                object.remove(key);
+            } else {
+                object.insert(key.clone(), field.clone());
+            }
+        }
+    }
+    *resume = Resume::deserialize(value).map_err(invalid)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> Resume {
+        Resume {
+            name: name.to_string(),
+            ..Resume::default()
+        }
+    }
+
+    /// Renames `resume` to `name`, recording the edit in `history`.
+    fn rename(history: &mut History, resume: &mut Resume, name: &str) {
+        let after = named(name);
+        history.record(format!("rename to {}", name), resume, &after);
+        *resume = after;
+    }
+
+    #[test]
+    fn undoes_and_redoes_in_order() {
+        let mut history = History::default();
+        let mut resume = named("a");
+        rename(&mut history, &mut resume, "b");
+        rename(&mut history, &mut resume, "c");
+
+        assert_eq!(history.undo(&mut resume).unwrap().as_deref(), Some("rename to c"));
+        assert_eq!(resume.name, "b");
+        assert_eq!(history.undo(&mut resume).unwrap().as_deref(), Some("rename to b"));
+        assert_eq!(resume.name, "a");
+        assert_eq!(history.undo(&mut resume).unwrap(), None);
+        assert!(!history.can_undo() && history.can_redo());
+
+        assert_eq!(history.redo(&mut resume).unwrap().as_deref(), Some("rename to b"));
+        assert_eq!(history.redo(&mut resume).unwrap().as_deref(), Some("rename to c"));
+        assert_eq!(resume, named("c"));
+        assert_eq!(history.redo(&mut resume).unwrap(), None);
+    }
+
+    #[test]
+    fn a_new_edit_clears_redo() {
+        let mut history = History::default();
+        let mut resume = named("a");
+        rename(&mut history, &mut resume, "b");
+        history.undo(&mut resume).unwrap();
+        rename(&mut history, &mut resume, "z");
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn edits_that_change_nothing_are_not_recorded() {
+        let mut history = History::default();
+        let mut resume = named("a");
+        rename(&mut history, &mut resume, "a");
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn keeps_only_the_changed_fields() {
+        let mut history = History::default();
+        let mut resume = named("a");
+        resume.website = Some("https://a.dev".to_string());
+        let mut after = resume.clone();
+        after.website = None;
+        history.record("clear website", &resume, &after);
+
+        let change = &history.undo[0];
+        assert_eq!(change.before.keys().collect::<Vec<_>>(), ["website"]);
+        history.undo(&mut after).unwrap();
+        assert_eq!(after, resume);
+    }
+
+    #[test]
+    fn drops_the_oldest_edits_past_the_limit() {
+        let mut history = History::new(3);
+        let mut resume = named("0");
+        for i in 1..=5 {
+            rename(&mut history, &mut resume, &i.to_string());
+        }
+        let mut undone = Vec::new();
+        while let Some(label) = history.undo(&mut resume).unwrap() {
+            undone.push(label);
+        }
+        assert_eq!(undone, ["rename to 5", "rename to 4", "rename to 3"]);
+        assert_eq!(resume.name, "2");
+    }
+
+    #[test]
+    fn drops_the_oldest_edits_past_the_size_limit() {
+        let mut history = History::default();
+        let mut resume = named("");
+        let big = "x".repeat(MAX_BYTES / 3);
+        for i in 0..4 {
+            rename(&mut history, &mut resume, &format!("{}{}", i, big));
+        }
+        let total: usize = history.undo.iter().map(|c| c.size).sum();
+        assert!(total <= MAX_BYTES);
+        assert!(history.undo.len() < 4 && history.can_undo());
+    }
+
+    #[test]
+    fn persisted_history_is_reused_only_for_matching_data() {
+        let path = std::env::temp_dir().join(format!("resume-utd-history-{}.json", std::process::id()));
+        let mut history = History::default();
+        let mut resume = named("a");
+        history.persist_to(path.clone(), &resume);
+        rename(&mut history, &mut resume, "b");
+        history.save(&resume).unwrap();
+
+        let mut reopened = History::default();
+        assert!(reopened.persist_to(path.clone(), &resume));
+        assert_eq!(reopened.undo(&mut resume).unwrap().as_deref(), Some("rename to b"));
+
+        // The file was edited elsewhere since, so the history no longer applies.
+        let mut stale = History::default();
+        assert!(!stale.persist_to(path.clone(), &named("edited by hand")));
+        assert!(!stale.can_undo());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,651 @@
+//! Conversion between `Resume` and the JSON Resume schema
+//! (https://jsonresume.org/schema), so data can be imported from and
+//! exported to the wider JSON Resume tooling and theme ecosystem.
+
+use crate::data::date::{DateBound, DateRange};
+use crate::data::manager::{
+    Contact, Education, Experience, Point, Profile, Project, Resume, Skill, SkillCategory,
+};
+use crate::data::migrate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct JsonResume {
+    #[serde(default)]
+    pub basics: Basics,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub work: Vec<Work>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub education: Vec<JsonEducation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<JsonProject>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<JsonSkill>,
+    /// Sections this tool has no equivalent for (awards, volunteer, ...).
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Basics {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<JsonProfile>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct JsonProfile {
+    #[serde(default)]
+    pub network: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Work {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub position: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonEducation {
+    #[serde(default)]
+    pub institution: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub study_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<String>,
+    #[serde(default)]
+    pub courses: Vec<String>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonProject {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct JsonSkill {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// Fields that had no equivalent on the other side of a conversion, as
+/// paths such as `basics.summary` or `experience[1].dates`.
+#[derive(Debug, Default)]
+pub struct ConversionReport {
+    pub unmapped: Vec<String>,
+}
+
+impl ConversionReport {
+    fn skip(&mut self, path: impl Into<String>) {
+        self.unmapped.push(path.into());
+    }
+
+    fn skip_extra(&mut self, prefix: &str, other: &BTreeMap<String, serde_json::Value>) {
+        for key in other.keys() {
+            self.skip(format!("{}{}", prefix, key));
+        }
+    }
+}
+
+/// Converts a JSON Resume document into this tool's `Resume`.
+pub fn to_resume(json: &JsonResume) -> (Resume, ConversionReport) {
+    let mut report = ConversionReport::default();
+    let basics = &json.basics;
+
+    if basics.label.is_some() {
+        report.skip("basics.label");
+    }
+    if basics.summary.is_some() {
+        report.skip("basics.summary");
+    }
+    report.skip_extra("basics.", &basics.other);
+    report.skip_extra("", &json.other);
+
+    let location = basics
+        .location
+        .as_ref()
+        .map(|l| {
+            [&l.address, &l.city, &l.region, &l.postal_code, &l.country_code]
+                .into_iter()
+                .flatten()
+                .filter(|part| !part.is_empty())
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default();
+
+    let contact = Contact {
+        email: basics.email.clone().unwrap_or_default(),
+        phone: basics.phone.clone().unwrap_or_default(),
+        location,
+        profiles: basics
+            .profiles
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                if p.username.is_some() {
+                    report.skip(format!("basics.profiles[{}].username", i));
+                }
+                Profile {
+                    label: p.network.clone(),
+                    url: p.url.clone(),
+                }
+            })
+            .collect(),
+    };
+
+    let education = json
+        .education
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            if e.score.is_some() {
+                report.skip(format!("education[{}].score", i));
+            }
+            report.skip_extra(&format!("education[{}].", i), &e.other);
+            let degree = [&e.study_type, &e.area]
+                .into_iter()
+                .flatten()
+                .filter(|part| !part.is_empty())
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            Education {
+                name: e.institution.clone(),
+                degree,
+                dates: dates_from_iso(&e.start_date, &e.end_date, &format!("education[{}]", i), &mut report),
+                points: e.courses.iter().map(|c| Point::from(c.as_str())).collect(),
+                tags: Vec::new(),
+            }
+        })
+        .collect();
+
+    let experience = json
+        .work
+        .iter()
+        .enumerate()
+        .map(|(i, w)| {
+            if w.url.is_some() {
+                report.skip(format!("work[{}].url", i));
+            }
+            report.skip_extra(&format!("work[{}].", i), &w.other);
+            let mut points = Vec::new();
+            if let Some(summary) = &w.summary
+                && !summary.is_empty()
+            {
+                points.push(Point::from(summary.as_str()));
+            }
+            points.extend(w.highlights.iter().map(|h| Point::from(h.as_str())));
+            Experience {
+                company: w.name.clone(),
+                position: w.position.clone(),
+                dates: dates_from_iso(&w.start_date, &w.end_date, &format!("work[{}]", i), &mut report),
+                points,
+                tags: Vec::new(),
+            }
+        })
+        .collect();
+
+    let projects = json
+        .projects
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            report.skip_extra(&format!("projects[{}].", i), &p.other);
+            let mut points = Vec::new();
+            if let Some(description) = &p.description
+                && !description.is_empty()
+            {
+                points.push(Point::from(description.as_str()));
+            }
+            points.extend(p.highlights.iter().map(|h| Point::from(h.as_str())));
+            Project {
+                name: p.name.clone(),
+                link: p.url.clone().filter(|url| !url.is_empty()),
+                points,
+                tags: Vec::new(),
+            }
+        })
+        .collect();
+
+    let skills = json
+        .skills
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            report.skip_extra(&format!("skills[{}].", i), &s.other);
+            SkillCategory {
+                name: s.name.clone(),
+                items: s
+                    .keywords
+                    .iter()
+                    .map(|k| Skill {
+                        name: k.clone(),
+                        proficiency: s.level.clone().filter(|level| !level.is_empty()),
+                    })
+                    .collect(),
+                tags: Vec::new(),
+            }
+        })
+        .collect();
+
+    let resume = Resume {
+        schema_version: migrate::CURRENT_VERSION,
+        name: basics.name.clone(),
+        contact,
+        website: basics.url.clone().filter(|url| !url.is_empty()),
+        education,
+        projects,
+        experience,
+        skills,
+        profiles: Vec::new(),
+    };
+
+    (resume, report)
+}
+
+/// Converts a `Resume` into a JSON Resume document.
+pub fn from_resume(resume: &Resume) -> (JsonResume, ConversionReport) {
+    let mut report = ConversionReport::default();
+    let contact = &resume.contact;
+
+    let location = if contact.location.is_empty() {
+        None
+    } else {
+        let mut location = Location::default();
+        match contact.location.split_once(',') {
+            Some((city, region)) => {
+                location.city = Some(city.trim().to_string());
+                location.region = Some(region.trim().to_string());
+            }
+            None => location.city = Some(contact.location.clone()),
+        }
+        Some(location)
+    };
+
+    let basics = Basics {
+        name: resume.name.clone(),
+        email: non_empty(&contact.email),
+        phone: non_empty(&contact.phone),
+        url: resume.website.clone(),
+        location,
+        profiles: contact
+            .profiles
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| {
+                // JSON Resume profiles are links, so one without a URL is left out.
+                if p.url.is_empty() {
+                    report.skip(format!("contact.profiles[{}]", i));
+                }
+                !p.url.is_empty()
+            })
+            .map(|(_, p)| JsonProfile {
+                network: p.label.clone(),
+                username: None,
+                url: p.url.clone(),
+            })
+            .collect(),
+        ..Basics::default()
+    };
+
+    let work = resume
+        .experience
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let (start_date, end_date) = dates_to_iso(&e.dates, &format!("experience[{}]", i), &mut report);
+            Work {
+                name: e.company.clone(),
+                position: e.position.clone(),
+                start_date,
+                end_date,
+                highlights: texts(&e.points),
+                ..Work::default()
+            }
+        })
+        .collect();
+
+    let education = resume
+        .education
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let (start_date, end_date) = dates_to_iso(&e.dates, &format!("education[{}]", i), &mut report);
+            JsonEducation {
+                institution: e.name.clone(),
+                study_type: non_empty(&e.degree),
+                start_date,
+                end_date,
+                courses: texts(&e.points),
+                ..JsonEducation::default()
+            }
+        })
+        .collect();
+
+    let projects = resume
+        .projects
+        .iter()
+        .map(|p| JsonProject {
+            name: p.name.clone(),
+            highlights: texts(&p.points),
+            url: p.link.clone(),
+            ..JsonProject::default()
+        })
+        .collect();
+
+    let skills = resume
+        .skills
+        .iter()
+        .enumerate()
+        .map(|(i, category)| {
+            // JSON Resume only has one level per skill group.
+            let mut levels: Vec<&String> = category.items.iter().filter_map(|s| s.proficiency.as_ref()).collect();
+            levels.dedup();
+            let level = match levels.as_slice() {
+                [] => None,
+                [level] if category.items.iter().all(|s| s.proficiency.is_some()) => Some((*level).clone()),
+                _ => {
+                    report.skip(format!("skills[{}].items[*].proficiency", i));
+                    None
+                }
+            };
+            JsonSkill {
+                name: category.name.clone(),
+                level,
+                keywords: category.items.iter().map(|s| s.name.clone()).collect(),
+                other: BTreeMap::new(),
+            }
+        })
+        .collect();
+
+    let json = JsonResume {
+        basics,
+        work,
+        education,
+        projects,
+        skills,
+        other: BTreeMap::new(),
+    };
+
+    (json, report)
+}
+
+fn texts(points: &[Point]) -> Vec<String> {
+    points.iter().map(|p| p.text.clone()).collect()
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Dates that do not parse are left out and noted in `report` under `path`.
+fn dates_from_iso(start: &Option<String>, end: &Option<String>, path: &str, report: &mut ConversionReport) -> DateRange {
+    let mut parse = |value: &str, field: &str| {
+        let bound = parse_iso(value);
+        if bound.is_none() && !value.trim().is_empty() {
+            report.skip(format!("{}.{}", path, field));
+        }
+        bound
+    };
+    let start = start.as_deref().and_then(|start| parse(start, "startDate"));
+    let end = match end.as_deref() {
+        Some(end) => parse(end, "endDate"),
+        // An entry with a start date and no end date is ongoing.
+        None if start.is_some() => Some(DateBound::Present),
+        None => None,
+    };
+    DateRange { start, end, text: None }
+}
+
+fn parse_iso(value: &str) -> Option<DateBound> {
+    // JSON Resume dates are "YYYY", "YYYY-MM" or "YYYY-MM-DD"; drop the day.
+    let trimmed = match value.match_indices('-').nth(1) {
+        Some((index, _)) => &value[..index],
+        None => value,
+    };
+    DateBound::parse(trimmed)
+}
+
+fn dates_to_iso(dates: &DateRange, path: &str, report: &mut ConversionReport) -> (Option<String>, Option<String>) {
+    if dates.text.is_some() {
+        report.skip(format!("{}.dates", path));
+    }
+    // JSON Resume reads a missing end date as "present", so an open-ended
+    // range cannot be told apart from an ongoing one.
+    if dates.start.is_some() && dates.end.is_none() {
+        report.skip(format!("{}.dates.end", path));
+    }
+    let iso = |bound: &Option<DateBound>| match bound {
+        Some(DateBound::Present) | None => None,
+        Some(bound) => Some(bound.to_string()),
+    };
+    (iso(&dates.start), iso(&dates.end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unparseable_dates_are_reported() {
+        let json = JsonResume {
+            work: vec![Work {
+                name: "Acme".to_string(),
+                start_date: Some("2021-03-15".to_string()),
+                end_date: Some("sometime".to_string()),
+                ..Work::default()
+            }],
+            education: vec![JsonEducation {
+                institution: "UT Dallas".to_string(),
+                start_date: Some("Fall '19".to_string()),
+                ..JsonEducation::default()
+            }],
+            ..JsonResume::default()
+        };
+        let (resume, report) = to_resume(&json);
+
+        assert_eq!(resume.experience[0].dates.start, Some(DateBound::YearMonth(2021, 3)));
+        assert_eq!(resume.experience[0].dates.end, None);
+        assert_eq!(resume.education[0].dates.start, None);
+        assert_eq!(report.unmapped, ["education[0].startDate", "work[0].endDate"]);
+    }
+
+    #[test]
+    fn profiles_losing_fields_are_reported() {
+        let mut json = JsonResume::default();
+        json.basics.profiles = vec![JsonProfile {
+            network: "GitHub".to_string(),
+            username: Some("ada".to_string()),
+            url: "https://github.com/ada".to_string(),
+        }];
+        let (resume, report) = to_resume(&json);
+        assert_eq!(resume.contact.profiles[0].url, "https://github.com/ada");
+        assert_eq!(report.unmapped, ["basics.profiles[0].username"]);
+
+        let mut resume = resume;
+        resume.contact.profiles.push(Profile {
+            label: "Blog".to_string(),
+            url: String::new(),
+        });
+        let (json, report) = from_resume(&resume);
+        assert_eq!(json.basics.profiles.len(), 1);
+        assert_eq!(report.unmapped, ["contact.profiles[1]"]);
+    }
+
+    fn sample() -> JsonResume {
+        serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Ada Lovelace",
+                "label": "Engineer",
+                "email": "ada@example.com",
+                "url": "https://ada.dev",
+                "location": { "city": "London", "region": "England" },
+                "profiles": [{ "network": "GitHub", "url": "https://github.com/ada" }]
+            },
+            "work": [{
+                "name": "Acme",
+                "position": "Engineer",
+                "startDate": "2022-01-10",
+                "summary": "Platform team",
+                "highlights": ["Cut build times in half"]
+            }],
+            "education": [{
+                "institution": "UT Dallas",
+                "studyType": "BS",
+                "area": "Computer Science",
+                "startDate": "2018",
+                "endDate": "2022",
+                "score": "3.9"
+            }],
+            "projects": [{ "name": "resume-utd", "url": "https://example.com", "highlights": ["TUI editor"] }],
+            "skills": [{ "name": "Languages", "level": "Advanced", "keywords": ["Rust", "Go"] }],
+            "awards": [{ "title": "Best in show" }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn imports_json_resume() {
+        let (resume, report) = to_resume(&sample());
+
+        assert_eq!(resume.schema_version, migrate::CURRENT_VERSION);
+        assert_eq!(resume.contact.location, "London, England");
+        assert_eq!(resume.website.as_deref(), Some("https://ada.dev"));
+        assert_eq!(resume.education[0].degree, "BS, Computer Science");
+        assert_eq!(resume.education[0].dates.to_string(), "2018 – 2022");
+        // No end date means the job is ongoing; the summary leads the bullets.
+        assert_eq!(resume.experience[0].dates.end, Some(DateBound::Present));
+        assert_eq!(texts(&resume.experience[0].points), ["Platform team", "Cut build times in half"]);
+        assert_eq!(resume.projects[0].link.as_deref(), Some("https://example.com"));
+        assert_eq!(resume.skills[0].items[1].proficiency.as_deref(), Some("Advanced"));
+
+        assert_eq!(report.unmapped, ["basics.label", "awards", "education[0].score"]);
+    }
+
+    #[test]
+    fn round_trips_through_json_resume() {
+        let (resume, _) = to_resume(&sample());
+        let (json, report) = from_resume(&resume);
+        let (again, _) = to_resume(&json);
+
+        assert!(report.unmapped.is_empty(), "{:?}", report.unmapped);
+        // The work summary comes back as the first highlight.
+        assert_eq!(again, resume);
+    }
+
+    #[test]
+    fn exports_report_what_json_resume_cannot_hold() {
+        let (mut resume, _) = to_resume(&sample());
+        resume.experience[0].dates = DateRange::parse("Summers, mostly");
+        resume.skills[0].items[0].proficiency = Some("Expert".to_string());
+
+        let (json, report) = from_resume(&resume);
+        assert_eq!(json.work[0].start_date, None);
+        assert_eq!(json.skills[0].level, None);
+        assert_eq!(report.unmapped, ["experience[0].dates", "skills[0].items[*].proficiency"]);
+    }
+
+    #[test]
+    fn open_ended_dates_are_reported_on_export() {
+        let (mut resume, _) = to_resume(&sample());
+        resume.experience[0].dates = DateRange::parse("2021");
+
+        let (json, report) = from_resume(&resume);
+        assert_eq!(json.work[0].start_date.as_deref(), Some("2021"));
+        assert_eq!(json.work[0].end_date, None);
+        assert_eq!(report.unmapped, ["experience[0].dates.end"]);
+    }
+
+    #[test]
+    fn unknown_skill_fields_are_reported() {
+        let json: JsonResume = serde_json::from_value(serde_json::json!({
+            "skills": [{ "name": "Languages", "keywords": ["Rust"], "level": "Advanced", "years": 5 }]
+        }))
+        .unwrap();
+        let (resume, report) = to_resume(&json);
+        assert_eq!(resume.skills[0].items[0].name, "Rust");
+        assert_eq!(report.unmapped, ["skills[0].years"]);
+    }
+}
@@ -1,133 +1,771 @@
+use crate::data::backup::{self, Backup};
+use crate::data::date::DateRange;
+use crate::data::error::DataError;
+use crate::data::format::DataFormat;
+use crate::data::history::History;
+use crate::data::json_resume::{self, ConversionReport, JsonResume};
+use crate::data::migrate;
+use crate::data::profile::{self, ResumeProfile};
+use crate::data::validate::{self, Issue};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::io;
+use std::path::Path;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Where the starter resume lives in the source tree; it is built into the
+/// binary so first runs work from any directory.
+const TEMPLATE_PATH: &str = "data/data_template.json";
+const TEMPLATE: &str = include_str!("../../data/data_template.json");
+
+/// A bullet point. Points without tags are stored as plain strings; tagged
+/// ones as `{ text, tags }`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(from = "PointRepr", into = "PointRepr")]
+pub struct Point {
+    pub text: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+enum PointRepr {
+    Text(String),
+    Tagged {
+        text: String,
+        #[serde(default)]
+        tags: Vec<String>,
+    },
+}
+
+impl From<PointRepr> for Point {
+    fn from(repr: PointRepr) -> Self {
+        match repr {
+            PointRepr::Text(text) => Point { text, tags: Vec::new() },
+            PointRepr::Tagged { text, tags } => Point { text, tags },
+        }
+    }
+}
+
+impl From<Point> for PointRepr {
+    fn from(point: Point) -> Self {
+        if point.tags.is_empty() {
+            PointRepr::Text(point.text)
+        } else {
+            PointRepr::Tagged {
+                text: point.text,
+                tags: point.tags,
+            }
+        }
+    }
+}
+
+impl From<String> for Point {
+    fn from(text: String) -> Self {
+        Point { text, tags: Vec::new() }
+    }
+}
+
+impl From<&str> for Point {
+    fn from(text: &str) -> Self {
+        Point::from(text.to_string())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Education {
     pub name: String,
     pub degree: String,
-    pub years: String,
-    pub points: Vec<String>,
+    pub dates: DateRange,
+    pub points: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Project {
     pub name: String,
     pub link: Option<String>,
-    pub points: Vec<String>,
+    pub points: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Experience {
     pub company: String,
     pub position: String,
-    pub years: String,
-    pub points: Vec<String>,
+    pub dates: DateRange,
+    pub points: Vec<Point>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Skill {
+    pub name: String,
+    pub proficiency: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SkillCategory {
+    pub name: String,
+    pub items: Vec<Skill>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Profile {
+    pub label: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Contact {
+    pub email: String,
+    pub phone: String,
+    pub location: String,
+    pub profiles: Vec<Profile>,
+}
+
+impl Contact {
+    /// Best-effort split of an old `contact` string such as
+    /// "jane@doe.dev | (555) 123-4567 | Dallas, TX" into structured fields.
+    pub fn from_legacy(contact: &str) -> Self {
+        let mut result = Contact::default();
+        let mut leftover = Vec::new();
+
+        for part in contact.split(['|', '•', '\n']).map(str::trim).filter(|p| !p.is_empty()) {
+            let digits = part.chars().filter(|c| c.is_ascii_digit()).count();
+            if part.contains('@') && !part.contains('/') && result.email.is_empty() {
+                result.email = part.to_string();
+            } else if part.contains("://") || part.starts_with("www.") || part.contains(".com/") {
+                result.profiles.push(Profile {
+                    label: profile_label(part),
+                    url: part.to_string(),
+                });
+            } else if digits >= 7 && result.phone.is_empty() {
+                result.phone = part.to_string();
+            } else {
+                leftover.push(part);
+            }
+        }
+
+        result.location = leftover.join(", ");
+        result
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+fn profile_label(url: &str) -> String {
+    let lower = url.to_lowercase();
+    if lower.contains("linkedin.") {
+        "LinkedIn".to_string()
+    } else if lower.contains("github.") {
+        "GitHub".to_string()
+    } else {
+        "Website".to_string()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Resume {
+    /// Version of the persisted layout; see `data::migrate`.
+    pub schema_version: u32,
     pub name: String,
-    pub contact: String,
+    pub contact: Contact,
     pub website: Option<String>,
     pub education: Vec<Education>,
     pub projects: Vec<Project>,
     pub experience: Vec<Experience>,
+    #[serde(default)]
+    pub skills: Vec<SkillCategory>,
+    /// Named variants of the resume; see `data::profile`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<ResumeProfile>,
+}
+
+impl Default for Resume {
+    fn default() -> Self {
+        Self {
+            schema_version: migrate::CURRENT_VERSION,
+            name: String::new(),
+            contact: Contact::default(),
+            website: None,
+            education: Vec::new(),
+            projects: Vec::new(),
+            experience: Vec::new(),
+            skills: Vec::new(),
+            profiles: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct DataManager {
     pub resume: Resume,
     pub file_path: String,
+    /// Number of rotating backups `save` keeps; 0 disables them.
+    pub keep_backups: usize,
+    /// The schema version the file had when it was upgraded on load, until
+    /// the upgraded data is saved over it.
+    pub migrated_from: Option<u32>,
+    /// Edits that can be undone and redone; see `data::history`.
+    pub history: History,
 }
 
 impl DataManager {
-    pub fn new(file_path: &str) -> io::Result<Self> {
-        let data_str = fs::read_to_string(file_path)?;
-        let resume: Resume = match serde_json::from_str(&data_str) {
-            Ok(r) => r,
-            Err(e) => {
-                eprintln!("Failed to parse resume JSON: {}", e);
-                std::process::exit(1);
-            }
-        };
+    /// Loads `file_path`, upgrading it in memory if it uses an older schema.
+    /// Nothing is written until `save`, which first copies the original
+    /// aside with `migrate::backup_original`.
+    pub fn new(file_path: &str) -> Result<Self, DataError> {
+        let path = Path::new(file_path);
+        let (resume, migrated_from) = read(path, DataFormat::from_path(path))?;
+
+        let mut data_manager = Self::with_resume(resume, file_path);
+        data_manager.migrated_from = migrated_from;
+        Ok(data_manager)
+    }
+
+    /// The starter resume built into the binary, to be saved to `file_path`.
+    pub fn from_template(file_path: &str) -> Result<Self, DataError> {
+        let (resume, _) = parse(TEMPLATE, Path::new(TEMPLATE_PATH), DataFormat::Json)?;
+        Ok(Self::with_resume(resume, file_path))
+    }
+
+    /// An empty resume that will be saved to `file_path`.
+    pub fn empty(file_path: &str) -> Self {
+        Self::with_resume(Resume::default(), file_path)
+    }
 
-        Ok(Self {
+    fn with_resume(resume: Resume, file_path: &str) -> Self {
+        Self {
             resume,
             file_path: file_path.to_string(),
-        })
+            keep_backups: backup::DEFAULT_KEEP,
+            migrated_from: None,
+            history: History::default(),
+        }
     }
 
+    /// Loads what it can from a file `new` rejected: each top-level section
+    /// that fails to parse is left empty and its name returned. If the file
+    /// is not even valid syntax, everything is dropped.
+    pub fn open_lenient(file_path: &str) -> Result<(Self, Vec<String>), DataError> {
+        let path = Path::new(file_path);
+        let data_str = fs::read_to_string(path).map_err(DataError::io(path))?;
+        let mut dropped = Vec::new();
 
-    pub fn save(&self) -> io::Result<()> {
-        let json_str = serde_json::to_string_pretty(&self.resume)?;
-        fs::write(&self.file_path, json_str)?;
-        Ok(())
+        let parsed = DataFormat::from_path(path).parse_value(&data_str).and_then(|mut value| {
+            migrate::migrate(&mut value)?;
+            Ok(value)
+        });
+        let resume = match parsed {
+            Ok(value) => Resume {
+                schema_version: migrate::CURRENT_VERSION,
+                name: section(&value, "name", &mut dropped),
+                contact: section(&value, "contact", &mut dropped),
+                website: section(&value, "website", &mut dropped),
+                education: section(&value, "education", &mut dropped),
+                projects: section(&value, "projects", &mut dropped),
+                experience: section(&value, "experience", &mut dropped),
+                skills: section(&value, "skills", &mut dropped),
+                profiles: section(&value, "profiles", &mut dropped),
+            },
+            Err(_) => {
+                dropped.push("entire file".to_string());
+                Resume::default()
+            }
+        };
+
+        Ok((Self::with_resume(resume, file_path), dropped))
     }
 
-    pub fn add_education(&mut self, education: Education) {
-        self.resume.education.push(education);
+    /// Reads a JSON Resume (jsonresume.org) file into a manager that will
+    /// save to `file_path`. The report lists fields that were dropped.
+    pub fn import_json_resume(input: &str, file_path: &str) -> Result<(Self, ConversionReport), DataError> {
+        let path = Path::new(input);
+        let data_str = fs::read_to_string(path).map_err(DataError::io(path))?;
+        let json: JsonResume = serde_json::from_str(&data_str).map_err(|e| DataError::Parse {
+            path: path.to_path_buf(),
+            format: DataFormat::Json,
+            line: Some(e.line()),
+            column: Some(e.column()),
+            message: e.to_string(),
+        })?;
+        let (resume, report) = json_resume::to_resume(&json);
+
+        Ok((Self::with_resume(resume, file_path), report))
     }
 
-    pub fn add_experience(&mut self, experience: Experience) {
-        self.resume.experience.push(experience);
+    /// Writes the resume back in the format matching `file_path`'s extension.
+    /// The previous contents are kept as a rotating backup, and the new ones
+    /// are written atomically. The undo history is written too if it is
+    /// persisted.
+    pub fn save(&mut self) -> Result<(), DataError> {
+        let path = Path::new(&self.file_path);
+        let data_str = self.format().serialize(&self.resume).map_err(DataError::io(path))?;
+        if let Some(version) = self.migrated_from {
+            migrate::backup_original(path, version)?;
+        }
+        backup::create(path, self.keep_backups)?;
+        backup::write_atomic(path, &data_str)?;
+        self.migrated_from = None;
+        self.history.save(&self.resume)
     }
 
-    pub fn add_project(&mut self, project: Project) {
-        self.resume.projects.push(project);
+    /// Problems in the current data; see `data::validate`.
+    pub fn validate(&self) -> Vec<Issue> {
+        validate::validate(&self.resume)
     }
 
-    pub fn update_education(&mut self, index: usize, education: Education) -> Result<(), String> {
-        if index < self.resume.education.len() {
-            self.resume.education[index] = education;
-            Ok(())
-        } else {
-            Err("Education index out of bounds".to_string())
+    pub fn format(&self) -> DataFormat {
+        DataFormat::from_path(Path::new(&self.file_path))
+    }
+
+    /// Backups of the data file, newest first.
+    pub fn backups(&self) -> Vec<Backup> {
+        backup::list(Path::new(&self.file_path))
+    }
+
+    /// Loads `backup` in place of `file_path`. Nothing is written until
+    /// `save`, which puts the restored data back at `file_path`.
+    pub fn from_backup(file_path: &str, backup: &Backup) -> Result<Self, DataError> {
+        // Backups end in `.bak`, so take the format from the data file.
+        let (resume, _) = read(&backup.path, DataFormat::from_path(Path::new(file_path)))?;
+        Ok(Self::with_resume(resume, file_path))
+    }
+
+    /// Writes the resume to `file_path` and keeps saving there from now on.
+    /// The format follows the new extension. On failure nothing changes.
+    pub fn save_as(&mut self, file_path: &str) -> Result<(), DataError> {
+        let path = Path::new(file_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(DataError::io(parent))?;
         }
+        let previous = std::mem::replace(&mut self.file_path, file_path.to_string());
+        // A file upgraded on load is left as it was, so it needs no copy.
+        let migrated_from = self.migrated_from.take();
+        self.history.relocate(History::path_for(path));
+        let result = self.save();
+        if result.is_err() {
+            self.history.relocate(History::path_for(Path::new(&previous)));
+            self.file_path = previous;
+            self.migrated_from = migrated_from;
+        }
+        result
     }
 
-    pub fn update_experience(&mut self, index: usize, experience: Experience) -> Result<(), String> {
-        if index < self.resume.experience.len() {
-            self.resume.experience[index] = experience;
+    /// Replaces the resume with what is on disk at `file_path`, as an edit
+    /// that can be undone.
+    pub fn revert(&mut self) -> Result<(), DataError> {
+        let path = Path::new(&self.file_path);
+        let (on_disk, _) = read(path, DataFormat::from_path(path))?;
+        self.change("revert to disk", |resume| {
+            *resume = on_disk;
             Ok(())
-        } else {
-            Err("Experience index out of bounds".to_string())
-        }
+        })
     }
 
-    pub fn update_project(&mut self, index: usize, project: Project) -> Result<(), String> {
-        if index < self.resume.projects.len() {
-            self.resume.projects[index] = project;
+    /// Replaces the resume with the contents of `backup`, as an edit that
+    /// can be undone. Nothing is written until `save`.
+    pub fn restore_backup(&mut self, backup: &Backup) -> Result<(), DataError> {
+        let (restored, _) = read(&backup.path, self.format())?;
+        self.change("restore backup", |resume| {
+            *resume = restored;
             Ok(())
-        } else {
-            Err("Project index out of bounds".to_string())
-        }
+        })
     }
 
-    pub fn delete_education(&mut self, index: usize) -> Result<(), String> {
-        if index < self.resume.education.len() {
-            self.resume.education.remove(index);
+    pub fn update_personal_info(&mut self, name: String, contact: Contact, website: Option<String>) -> Result<(), DataError> {
+        require(&name, "Name")?;
+        self.change("edit personal info", |resume| {
+            resume.name = name;
+            resume.contact = contact;
+            resume.website = website;
             Ok(())
-        } else {
-            Err("Education index out of bounds".to_string())
-        }
+        })
     }
 
-    pub fn delete_experience(&mut self, index: usize) -> Result<(), String> {
-        if index < self.resume.experience.len() {
-            self.resume.experience.remove(index);
+    pub fn add_education(&mut self, education: Education) -> Result<(), DataError> {
+        require(&education.name, "School name")?;
+        self.change("add education", |resume| {
+            resume.education.push(education);
             Ok(())
-        } else {
-            Err("Experience index out of bounds".to_string())
-        }
+        })
     }
 
-    pub fn delete_project(&mut self, index: usize) -> Result<(), String> {
-        if index < self.resume.projects.len() {
-            self.resume.projects.remove(index);
+    pub fn add_experience(&mut self, experience: Experience) -> Result<(), DataError> {
+        require(&experience.company, "Company")?;
+        self.change("add experience", |resume| {
+            resume.experience.push(experience);
             Ok(())
-        } else {
-            Err("Project index out of bounds".to_string())
+        })
+    }
+
+    pub fn add_project(&mut self, project: Project) -> Result<(), DataError> {
+        require(&project.name, "Project name")?;
+        self.change("add project", |resume| {
+            resume.projects.push(project);
+            Ok(())
+        })
+    }
+
+    pub fn add_skill_category(&mut self, category: SkillCategory) -> Result<(), DataError> {
+        require(&category.name, "Category")?;
+        self.change("add skill category", |resume| {
+            resume.skills.push(category);
+            Ok(())
+        })
+    }
+
+    pub fn update_education(&mut self, index: usize, education: Education) -> Result<(), DataError> {
+        require(&education.name, "School name")?;
+        self.change("edit education", |resume| replace_item(&mut resume.education, "Education", index, education))
+    }
+
+    pub fn update_experience(&mut self, index: usize, experience: Experience) -> Result<(), DataError> {
+        require(&experience.company, "Company")?;
+        self.change("edit experience", |resume| replace_item(&mut resume.experience, "Experience", index, experience))
+    }
+
+    pub fn update_project(&mut self, index: usize, project: Project) -> Result<(), DataError> {
+        require(&project.name, "Project name")?;
+        self.change("edit project", |resume| replace_item(&mut resume.projects, "Project", index, project))
+    }
+
+    pub fn update_skill_category(&mut self, index: usize, category: SkillCategory) -> Result<(), DataError> {
+        require(&category.name, "Category")?;
+        self.change("edit skill category", |resume| replace_item(&mut resume.skills, "Skill category", index, category))
+    }
+
+    pub fn delete_education(&mut self, index: usize) -> Result<(), DataError> {
+        self.change("delete education", |resume| remove_item(&mut resume.education, "Education", index))
+    }
+
+    pub fn delete_experience(&mut self, index: usize) -> Result<(), DataError> {
+        self.change("delete experience", |resume| remove_item(&mut resume.experience, "Experience", index))
+    }
+
+    pub fn delete_project(&mut self, index: usize) -> Result<(), DataError> {
+        self.change("delete project", |resume| remove_item(&mut resume.projects, "Project", index))
+    }
+
+    pub fn delete_skill_category(&mut self, index: usize) -> Result<(), DataError> {
+        self.change("delete skill category", |resume| remove_item(&mut resume.skills, "Skill category", index))
+    }
+
+    /// Moves the education entry at `from` to `to`, shifting the ones between.
+    pub fn move_education(&mut self, from: usize, to: usize) -> Result<(), DataError> {
+        self.change("move education", |resume| move_item(&mut resume.education, "Education", from, to))
+    }
+
+    pub fn move_experience(&mut self, from: usize, to: usize) -> Result<(), DataError> {
+        self.change("move experience", |resume| move_item(&mut resume.experience, "Experience", from, to))
+    }
+
+    pub fn move_project(&mut self, from: usize, to: usize) -> Result<(), DataError> {
+        self.change("move project", |resume| move_item(&mut resume.projects, "Project", from, to))
+    }
+
+    pub fn move_skill_category(&mut self, from: usize, to: usize) -> Result<(), DataError> {
+        self.change("move skill category", |resume| move_item(&mut resume.skills, "Skill category", from, to))
+    }
+
+    /// Inserts a copy of the education entry at `index` right after it and
+    /// returns the copy's index.
+    pub fn duplicate_education(&mut self, index: usize) -> Result<usize, DataError> {
+        self.change("duplicate education", |resume| duplicate_item(&mut resume.education, "Education", index))
+    }
+
+    pub fn duplicate_experience(&mut self, index: usize) -> Result<usize, DataError> {
+        self.change("duplicate experience", |resume| duplicate_item(&mut resume.experience, "Experience", index))
+    }
+
+    pub fn duplicate_project(&mut self, index: usize) -> Result<usize, DataError> {
+        self.change("duplicate project", |resume| duplicate_item(&mut resume.projects, "Project", index))
+    }
+
+    pub fn duplicate_skill_category(&mut self, index: usize) -> Result<usize, DataError> {
+        self.change("duplicate skill category", |resume| duplicate_item(&mut resume.skills, "Skill category", index))
+    }
+
+    /// The profile called `name`, compared case-insensitively.
+    pub fn profile(&self, name: &str) -> Result<&ResumeProfile, DataError> {
+        self.resume
+            .profiles
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let known: Vec<&str> = self.resume.profiles.iter().map(|p| p.name.as_str()).collect();
+                if known.is_empty() {
+                    DataError::Validation(format!("no profile named '{}'; the data file defines none", name))
+                } else {
+                    DataError::Validation(format!("no profile named '{}'; expected one of: {}", name, known.join(", ")))
+                }
+            })
+    }
+
+    pub fn add_profile(&mut self, profile: ResumeProfile) -> Result<(), DataError> {
+        require(&profile.name, "Profile name")?;
+        self.require_unique_profile(&profile.name, None)?;
+        self.change("add profile", |resume| {
+            resume.profiles.push(profile);
+            Ok(())
+        })
+    }
+
+    pub fn update_profile(&mut self, index: usize, profile: ResumeProfile) -> Result<(), DataError> {
+        require(&profile.name, "Profile name")?;
+        self.require_unique_profile(&profile.name, Some(index))?;
+        self.change("edit profile", |resume| replace_item(&mut resume.profiles, "Profile", index, profile))
+    }
+
+    pub fn delete_profile(&mut self, index: usize) -> Result<(), DataError> {
+        self.change("delete profile", |resume| remove_item(&mut resume.profiles, "Profile", index))
+    }
+
+    /// Reverts the latest edit and returns its label, or `None` if there is
+    /// nothing to undo. Nothing is written until `save`.
+    pub fn undo(&mut self) -> Result<Option<String>, DataError> {
+        self.history.undo(&mut self.resume)
+    }
+
+    /// Re-applies the latest undone edit and returns its label.
+    pub fn redo(&mut self) -> Result<Option<String>, DataError> {
+        self.history.redo(&mut self.resume)
+    }
+
+    /// Keeps the undo history in `<file>.history.json` across sessions. Returns
+    /// whether a history from an earlier session was picked up.
+    pub fn persist_history(&mut self) -> bool {
+        let path = History::path_for(Path::new(&self.file_path));
+        self.history.persist_to(path, &self.resume)
+    }
+
+    /// Runs `edit` on the resume and records it in the history if it
+    /// succeeds. `edit` must leave the resume untouched when it fails.
+    fn change<T>(&mut self, label: &str, edit: impl FnOnce(&mut Resume) -> Result<T, DataError>) -> Result<T, DataError> {
+        let before = self.resume.clone();
+        let result = edit(&mut self.resume)?;
+        self.history.record(label, &before, &self.resume);
+        Ok(result)
+    }
+
+    /// Profiles are exported to directories named after them, so two names
+    /// that map to the same directory are refused as well as equal ones.
+    fn require_unique_profile(&self, name: &str, except: Option<usize>) -> Result<(), DataError> {
+        let slug = profile::slug(name);
+        let taken = self
+            .resume
+            .profiles
+            .iter()
+            .enumerate()
+            .find(|(i, p)| Some(*i) != except && profile::slug(&p.name) == slug);
+        match taken {
+            Some((_, p)) if p.name.eq_ignore_ascii_case(name) => {
+                Err(DataError::Validation(format!("a profile named '{}' already exists", name)))
+            }
+            Some((_, p)) => Err(DataError::Validation(format!(
+                "profile '{}' would export to the same files as '{}'",
+                name, p.name
+            ))),
+            None => Ok(()),
         }
     }
 }
+
+/// Reads and, if needed, migrates a data file. Returns the version it was
+/// migrated from, if any.
+fn read(path: &Path, format: DataFormat) -> Result<(Resume, Option<u32>), DataError> {
+    let data_str = fs::read_to_string(path).map_err(DataError::io(path))?;
+    parse(&data_str, path, format)
+}
+
+/// Parses and, if needed, migrates `data_str`, read from `path`.
+fn parse(data_str: &str, path: &Path, format: DataFormat) -> Result<(Resume, Option<u32>), DataError> {
+    let mut value = format.parse_value(data_str).map_err(|e| with_path(e, path))?;
+
+    match migrate::migrate(&mut value)? {
+        // Parse current files straight from the text so errors keep their
+        // line and column.
+        None => Ok((format.parse(data_str).map_err(|e| with_path(e, path))?, None)),
+        Some(version) => {
+            let resume = Resume::deserialize(&value).map_err(|e| DataError::Parse {
+                path: path.to_path_buf(),
+                format,
+                line: None,
+                column: None,
+                message: format!("after upgrading from schema version {}: {}", version, e),
+            })?;
+            Ok((resume, Some(version)))
+        }
+    }
+}
+
+fn with_path(error: DataError, path: &Path) -> DataError {
+    match error {
+        DataError::Parse { format, line, column, message, .. } => DataError::Parse {
+            path: path.to_path_buf(),
+            format,
+            line,
+            column,
+            message,
+        },
+        other => other,
+    }
+}
+
+fn section<T: DeserializeOwned + Default>(value: &serde_json::Value, key: &str, dropped: &mut Vec<String>) -> T {
+    match value.get(key) {
+        Some(field) => T::deserialize(field).unwrap_or_else(|_| {
+            dropped.push(key.to_string());
+            T::default()
+        }),
+        None => T::default(),
+    }
+}
+
+fn require(value: &str, field: &str) -> Result<(), DataError> {
+    if value.trim().is_empty() {
+        Err(DataError::Validation(format!("{} is required", field)))
+    } else {
+        Ok(())
+    }
+}
+
+fn out_of_range(section: &'static str, index: usize, len: usize) -> DataError {
+    DataError::IndexOutOfRange { section, index, len }
+}
+
+fn check_index(section: &'static str, index: usize, len: usize) -> Result<(), DataError> {
+    if index < len {
+        Ok(())
+    } else {
+        Err(out_of_range(section, index, len))
+    }
+}
+
+fn replace_item<T>(items: &mut [T], section: &'static str, index: usize, item: T) -> Result<(), DataError> {
+    let len = items.len();
+    let slot = items.get_mut(index).ok_or(out_of_range(section, index, len))?;
+    *slot = item;
+    Ok(())
+}
+
+fn remove_item<T>(items: &mut Vec<T>, section: &'static str, index: usize) -> Result<(), DataError> {
+    check_index(section, index, items.len())?;
+    items.remove(index);
+    Ok(())
+}
+
+fn move_item<T>(items: &mut Vec<T>, section: &'static str, from: usize, to: usize) -> Result<(), DataError> {
+    check_index(section, from, items.len())?;
+    check_index(section, to, items.len())?;
+    let item = items.remove(from);
+    items.insert(to, item);
+    Ok(())
+}
+
+fn duplicate_item<T: Clone>(items: &mut Vec<T>, section: &'static str, index: usize) -> Result<usize, DataError> {
+    let item = items.get(index).cloned().ok_or(out_of_range(section, index, items.len()))?;
+    items.insert(index + 1, item);
+    Ok(index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_is_built_in_and_current() {
+        let data_manager = DataManager::from_template("elsewhere/resume.yaml").unwrap();
+        assert_eq!(data_manager.file_path, "elsewhere/resume.yaml");
+        assert_eq!(data_manager.resume.schema_version, migrate::CURRENT_VERSION);
+        assert_eq!(data_manager.migrated_from, None);
+    }
+
+    #[test]
+    fn old_files_are_only_backed_up_when_saved() {
+        let dir = std::env::temp_dir().join(format!("resume-utd-migrate-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("resume.json");
+        let backup = dir.join("resume.json.v0.bak");
+        let original = r#"{"name": "Ada", "contact": "ada@example.com", "education": [], "projects": [], "experience": []}"#;
+        fs::write(&file, original).unwrap();
+
+        let mut data_manager = DataManager::new(file.to_str().unwrap()).unwrap();
+        data_manager.keep_backups = 0;
+        assert_eq!(data_manager.migrated_from, Some(0));
+        assert!(!backup.exists());
+
+        data_manager.save().unwrap();
+        assert_eq!(fs::read_to_string(&backup).unwrap(), original);
+        assert_eq!(data_manager.migrated_from, None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn with_projects(names: &[&str]) -> DataManager {
+        let mut data_manager = DataManager::empty("resume.json");
+        for name in names {
+            data_manager
+                .add_project(Project {
+                    name: name.to_string(),
+                    link: None,
+                    points: vec![Point::from(format!("{} point", name))],
+                    tags: Vec::new(),
+                })
+                .unwrap();
+        }
+        data_manager
+    }
+
+    fn project_names(data_manager: &DataManager) -> Vec<&str> {
+        data_manager.resume.projects.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    #[test]
+    fn moves_entries_shifting_the_ones_between() {
+        let mut data_manager = with_projects(&["a", "b", "c", "d"]);
+        data_manager.move_project(0, 2).unwrap();
+        assert_eq!(project_names(&data_manager), ["b", "c", "a", "d"]);
+        data_manager.move_project(3, 0).unwrap();
+        assert_eq!(project_names(&data_manager), ["d", "b", "c", "a"]);
+        data_manager.move_project(1, 1).unwrap();
+        assert_eq!(project_names(&data_manager), ["d", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn moving_out_of_range_changes_nothing() {
+        let mut data_manager = with_projects(&["a", "b"]);
+        let err = data_manager.move_project(0, 2).unwrap_err();
+        assert!(matches!(err, DataError::IndexOutOfRange { index: 2, len: 2, .. }));
+        assert!(data_manager.move_project(5, 0).is_err());
+        assert_eq!(project_names(&data_manager), ["a", "b"]);
+    }
+
+    #[test]
+    fn duplicates_right_after_the_original() {
+        let mut data_manager = with_projects(&["a", "b"]);
+        assert_eq!(data_manager.duplicate_project(0).unwrap(), 1);
+        assert_eq!(project_names(&data_manager), ["a", "a", "b"]);
+        assert_eq!(data_manager.resume.projects[1], data_manager.resume.projects[0]);
+        assert!(data_manager.duplicate_project(3).is_err());
+        assert_eq!(data_manager.resume.projects.len(), 3);
+    }
+
+    #[test]
+    fn moves_and_copies_can_be_undone() {
+        let mut data_manager = with_projects(&["a", "b", "c"]);
+        data_manager.move_project(2, 0).unwrap();
+        data_manager.duplicate_project(0).unwrap();
+        data_manager.delete_project(3).unwrap();
+        assert_eq!(project_names(&data_manager), ["c", "c", "a"]);
+
+        assert_eq!(data_manager.undo().unwrap().as_deref(), Some("delete project"));
+        assert_eq!(data_manager.undo().unwrap().as_deref(), Some("duplicate project"));
+        assert_eq!(data_manager.undo().unwrap().as_deref(), Some("move project"));
+        assert_eq!(project_names(&data_manager), ["a", "b", "c"]);
+    }
+}
+
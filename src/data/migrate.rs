@@ -0,0 +1,204 @@
+//! Upgrades older resume documents to the current schema, one version at a
+//! time, before they are deserialized into `Resume`.
+//!
+//! To change the persisted shape: bump `CURRENT_VERSION` and append a step
+//! to `MIGRATIONS` that takes documents from the previous version to it.
+
+use crate::data::date::DateRange;
+use crate::data::error::DataError;
+use crate::data::manager::Contact;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const CURRENT_VERSION: u32 = 2;
+
+/// One upgrade step, from version `from` to `from + 1`.
+struct Migration {
+    from: u32,
+    apply: fn(&mut Map<String, Value>),
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 0,
+        apply: structured_contact_and_dates,
+    },
+    Migration {
+        from: 1,
+        apply: tags_and_profiles,
+    },
+];
+
+/// The schema version a document declares; files from before versioning
+/// have none and count as version 0. Anything but a whole number that fits
+/// a `u32` is an error rather than a guess.
+pub fn version_of(document: &Value) -> Result<u32, DataError> {
+    let Some(value) = document.get("schema_version") else {
+        return Ok(0);
+    };
+    value
+        .as_u64()
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| {
+            DataError::Validation(format!("schema_version must be a whole number from 0 to {}, not {}", u32::MAX, value))
+        })
+}
+
+/// Brings `document` up to `CURRENT_VERSION` in place. Returns the version
+/// it started at when any step ran.
+pub fn migrate(document: &mut Value) -> Result<Option<u32>, DataError> {
+    let start = version_of(document)?;
+    if start > CURRENT_VERSION {
+        return Err(DataError::Validation(format!(
+            "data uses schema version {}, but this build only understands up to {}; please upgrade resume-utd",
+            start, CURRENT_VERSION
+        )));
+    }
+    if start == CURRENT_VERSION {
+        return Ok(None);
+    }
+
+    let Some(object) = document.as_object_mut() else {
+        return Err(DataError::Validation("resume data must be a map of fields".to_string()));
+    };
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= start) {
+        (migration.apply)(object);
+        object.insert("schema_version".to_string(), Value::from(migration.from + 1));
+    }
+    Ok(Some(start))
+}
+
+/// Copies the pre-migration file to `<file>.v<version>.bak`, once; an
+/// existing copy is the true original and is never overwritten.
+pub fn backup_original(file_path: &Path, version: u32) -> Result<PathBuf, DataError> {
+    let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("data");
+    let backup = file_path.with_file_name(format!("{}.v{}.bak", file_name, version));
+    if !backup.exists() {
+        fs::copy(file_path, &backup).map_err(DataError::io(&backup))?;
+    }
+    Ok(backup)
+}
+
+/// v0 -> v1: `contact` becomes an object, free-form `years` strings become
+/// structured `dates`, and the `skills` section is added.
+fn structured_contact_and_dates(resume: &mut Map<String, Value>) {
+    if let Some(Value::String(contact)) = resume.get("contact") {
+        let contact = Contact::from_legacy(contact);
+        resume.insert("contact".to_string(), to_value(&contact));
+    }
+
+    for section in ["education", "experience"] {
+        let Some(Value::Array(entries)) = resume.get_mut(section) else {
+            continue;
+        };
+        for entry in entries.iter_mut().filter_map(Value::as_object_mut) {
+            let years = entry.remove("years");
+            let dates = entry.remove("dates").or(years);
+            let dates = match dates {
+                Some(Value::String(text)) => to_value(&DateRange::parse(&text)),
+                Some(other) => other,
+                None => to_value(&DateRange::default()),
+            };
+            entry.insert("dates".to_string(), dates);
+        }
+    }
+
+    resume.entry("skills").or_insert_with(|| Value::Array(Vec::new()));
+}
+
+/// v1 -> v2: entries gain `tags`, bullet points may be `{ text, tags }`
+/// objects and `profiles` is added. All are optional, so v1 data is already
+/// valid v2; the bump keeps older builds from misreading tagged points.
+fn tags_and_profiles(_resume: &mut Map<String, Value>) {}
+
+fn to_value<T: serde::Serialize>(value: &T) -> Value {
+    // Serializing these plain structs to a `Value` cannot fail.
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::date::DateBound;
+    use crate::data::manager::Resume;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    fn v0() -> Value {
+        json!({
+            "name": "Ada Lovelace",
+            "contact": "ada@example.com | 555-123-4567 | London, UK | https://github.com/ada",
+            "education": [{ "name": "UT Dallas", "degree": "BS", "years": "Aug 2021 - May 2025", "points": [] }],
+            "experience": [{ "company": "Acme", "position": "Intern", "years": "Summers, mostly", "points": ["Did things"] }],
+            "projects": []
+        })
+    }
+
+    #[test]
+    fn upgrades_v0_to_current() {
+        let mut document = v0();
+        assert_eq!(migrate(&mut document).unwrap(), Some(0));
+        assert_eq!(version_of(&document).unwrap(), CURRENT_VERSION);
+
+        let resume = Resume::deserialize(&document).unwrap();
+        assert_eq!(resume.contact.email, "ada@example.com");
+        assert_eq!(resume.contact.phone, "555-123-4567");
+        assert_eq!(resume.contact.location, "London, UK");
+        assert_eq!(resume.contact.profiles[0].url, "https://github.com/ada");
+        assert_eq!(resume.education[0].dates.start, Some(DateBound::YearMonth(2021, 8)));
+        assert_eq!(resume.education[0].dates.end, Some(DateBound::YearMonth(2025, 5)));
+        // Unparseable years are kept as text rather than lost.
+        assert_eq!(resume.experience[0].dates.text.as_deref(), Some("Summers, mostly"));
+        assert!(resume.skills.is_empty());
+    }
+
+    #[test]
+    fn upgrades_v1_to_v2_without_changes() {
+        let mut document = v0();
+        let object = document.as_object_mut().unwrap();
+        structured_contact_and_dates(object);
+        object.insert("schema_version".to_string(), Value::from(1));
+        let v1 = document.clone();
+
+        assert_eq!(migrate(&mut document).unwrap(), Some(1));
+        assert_eq!(document["schema_version"], 2);
+        document["schema_version"] = json!(1);
+        assert_eq!(document, v1);
+    }
+
+    #[test]
+    fn leaves_current_documents_alone() {
+        let mut document = json!({ "schema_version": CURRENT_VERSION, "name": "Ada" });
+        let before = document.clone();
+        assert_eq!(migrate(&mut document).unwrap(), None);
+        assert_eq!(document, before);
+    }
+
+    #[test]
+    fn refuses_newer_versions_and_non_objects() {
+        assert!(migrate(&mut json!({ "schema_version": CURRENT_VERSION + 1 })).is_err());
+        assert!(migrate(&mut json!(["not", "a", "resume"])).is_err());
+    }
+
+    #[test]
+    fn refuses_versions_that_are_not_a_u32() {
+        let too_big = u64::from(u32::MAX) + 1;
+        for version in [json!(too_big), json!(u64::MAX), json!(-1), json!(1.5), json!("2"), json!(null)] {
+            let mut document = json!({ "schema_version": version, "name": "Ada" });
+            let before = document.clone();
+            match migrate(&mut document) {
+                Err(DataError::Validation(message)) => assert!(message.contains("schema_version"), "{}", message),
+                other => panic!("{} was accepted: {:?}", version, other),
+            }
+            assert_eq!(document, before);
+        }
+        assert_eq!(version_of(&json!({ "name": "Ada" })).unwrap(), 0);
+    }
+
+    #[test]
+    fn every_version_has_a_step() {
+        let steps: Vec<u32> = MIGRATIONS.iter().map(|m| m.from).collect();
+        assert_eq!(steps, (0..CURRENT_VERSION).collect::<Vec<_>>());
+    }
+}
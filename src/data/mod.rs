@@ -1,3 +1,14 @@
+pub mod backup;
+pub mod date;
+pub mod error;
+pub mod format;
+pub mod history;
+pub mod json_resume;
 pub mod manager;
+pub mod migrate;
+pub mod profile;
+pub mod validate;
 
+pub use error::DataError;
+pub use format::DataFormat;
 pub use manager::DataManager;
@@ -0,0 +1,277 @@
+//! Named variants of one resume, e.g. "backend" or "research". A profile
+//! picks which sections appear and in what order, and which tagged entries
+//! and bullet points are included.
+//!
+//! Untagged entries and points belong to every profile. Tagged ones are
+//! included only by profiles that list at least one of their tags.
+
+use crate::data::manager::{Point, Resume};
+use serde::{Deserialize, Serialize};
+
+/// The sections a profile can select and order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Section {
+    Education,
+    Experience,
+    Projects,
+    Skills,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ResumeProfile {
+    pub name: String,
+    /// Sections in render order; empty means all of them in the default order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<Section>,
+    /// Tags to include; empty means every entry and point.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Section {
+    pub fn all() -> Vec<Section> {
+        vec![Section::Education, Section::Experience, Section::Projects, Section::Skills]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Section::Education => "education",
+            Section::Experience => "experience",
+            Section::Projects => "projects",
+            Section::Skills => "skills",
+        }
+    }
+}
+
+impl ResumeProfile {
+    /// The sections to render, in order, without repeats.
+    pub fn sections(&self) -> Vec<Section> {
+        if self.sections.is_empty() {
+            return Section::all();
+        }
+        let mut sections = Vec::new();
+        for section in &self.sections {
+            if !sections.contains(section) {
+                sections.push(*section);
+            }
+        }
+        sections
+    }
+
+    /// Whether something carrying `tags` belongs in this profile.
+    pub fn includes(&self, tags: &[String]) -> bool {
+        self.tags.is_empty()
+            || tags.is_empty()
+            || tags.iter().any(|tag| self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+    }
+
+    fn points(&self, points: &[Point]) -> Vec<Point> {
+        points
+            .iter()
+            .filter(|point| self.includes(&point.tags))
+            .map(|point| Point::from(point.text.clone()))
+            .collect()
+    }
+}
+
+/// The part of `resume` that `profile` selects, with tags stripped so every
+/// bullet point renders as plain text. `None` selects everything.
+pub fn apply(resume: &Resume, profile: Option<&ResumeProfile>) -> Resume {
+    let everything = ResumeProfile::default();
+    let profile = profile.unwrap_or(&everything);
+    let sections = profile.sections();
+    let mut view = Resume {
+        education: Vec::new(),
+        experience: Vec::new(),
+        projects: Vec::new(),
+        skills: Vec::new(),
+        profiles: Vec::new(),
+        ..resume.clone()
+    };
+
+    if sections.contains(&Section::Education) {
+        for education in resume.education.iter().filter(|e| profile.includes(&e.tags)) {
+            let mut education = education.clone();
+            education.points = profile.points(&education.points);
+            education.tags.clear();
+            view.education.push(education);
+        }
+    }
+    if sections.contains(&Section::Experience) {
+        for experience in resume.experience.iter().filter(|e| profile.includes(&e.tags)) {
+            let mut experience = experience.clone();
+            experience.points = profile.points(&experience.points);
+            experience.tags.clear();
+            view.experience.push(experience);
+        }
+    }
+    if sections.contains(&Section::Projects) {
+        for project in resume.projects.iter().filter(|p| profile.includes(&p.tags)) {
+            let mut project = project.clone();
+            project.points = profile.points(&project.points);
+            project.tags.clear();
+            view.projects.push(project);
+        }
+    }
+    if sections.contains(&Section::Skills) {
+        for category in resume.skills.iter().filter(|c| profile.includes(&c.tags)) {
+            let mut category = category.clone();
+            category.tags.clear();
+            view.skills.push(category);
+        }
+    }
+    view
+}
+
+/// A profile name made safe for file and directory names. Names that differ
+/// only in case or punctuation, like "back end" and "Back-End", share one.
+pub fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Every tag used by an entry or bullet point, in first-seen order.
+pub fn tags_in_use(resume: &Resume) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut add = |list: &[String]| {
+        for tag in list {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.clone());
+            }
+        }
+    };
+    for education in &resume.education {
+        add(&education.tags);
+        education.points.iter().for_each(|p| add(&p.tags));
+    }
+    for experience in &resume.experience {
+        add(&experience.tags);
+        experience.points.iter().for_each(|p| add(&p.tags));
+    }
+    for project in &resume.projects {
+        add(&project.tags);
+        project.points.iter().for_each(|p| add(&p.tags));
+    }
+    for category in &resume.skills {
+        add(&category.tags);
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::manager::{Experience, Project, SkillCategory};
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    fn point(text: &str, with: &[&str]) -> Point {
+        Point {
+            text: text.to_string(),
+            tags: tags(with),
+        }
+    }
+
+    fn experience(company: &str, points: Vec<Point>, with: &[&str]) -> Experience {
+        Experience {
+            company: company.to_string(),
+            position: "Engineer".to_string(),
+            dates: Default::default(),
+            points,
+            tags: tags(with),
+        }
+    }
+
+    fn resume() -> Resume {
+        Resume {
+            name: "Ada".to_string(),
+            experience: vec![
+                experience(
+                    "Acme",
+                    vec![point("Built APIs", &["backend"]), point("Built UIs", &["frontend"]), point("Led team", &[])],
+                    &[],
+                ),
+                experience("Pixel", Vec::new(), &["frontend"]),
+            ],
+            projects: vec![Project {
+                name: "resume-utd".to_string(),
+                link: None,
+                points: Vec::new(),
+                tags: tags(&["Backend"]),
+            }],
+            skills: vec![SkillCategory {
+                name: "Languages".to_string(),
+                items: Vec::new(),
+                tags: tags(&["backend"]),
+            }],
+            profiles: vec![ResumeProfile {
+                name: "backend".to_string(),
+                ..ResumeProfile::default()
+            }],
+            ..Resume::default()
+        }
+    }
+
+    fn profile(sections: &[Section], with: &[&str]) -> ResumeProfile {
+        ResumeProfile {
+            name: "test".to_string(),
+            sections: sections.to_vec(),
+            tags: tags(with),
+        }
+    }
+
+    #[test]
+    fn no_profile_keeps_everything_but_tags() {
+        let view = apply(&resume(), None);
+        assert_eq!(view.experience.len(), 2);
+        assert_eq!(view.experience[0].points.len(), 3);
+        assert_eq!(view.projects.len(), 1);
+        assert!(view.experience[0].points.iter().all(|p| p.tags.is_empty()));
+        assert!(view.projects[0].tags.is_empty() && view.skills[0].tags.is_empty());
+        assert!(view.profiles.is_empty());
+        assert_eq!(view.name, "Ada");
+    }
+
+    #[test]
+    fn tags_filter_entries_and_points() {
+        let view = apply(&resume(), Some(&profile(&[], &["backend"])));
+        let companies: Vec<&str> = view.experience.iter().map(|e| e.company.as_str()).collect();
+        assert_eq!(companies, ["Acme"]);
+        let points: Vec<&str> = view.experience[0].points.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(points, ["Built APIs", "Led team"]);
+        // Tags match regardless of case.
+        assert_eq!(view.projects.len(), 1);
+        assert_eq!(view.skills.len(), 1);
+    }
+
+    #[test]
+    fn sections_limit_what_is_kept() {
+        let view = apply(&resume(), Some(&profile(&[Section::Skills, Section::Experience], &[])));
+        assert!(view.education.is_empty() && view.projects.is_empty());
+        assert_eq!(view.experience.len(), 2);
+        assert_eq!(view.skills.len(), 1);
+    }
+
+    #[test]
+    fn sections_default_to_all_and_drop_repeats() {
+        assert_eq!(profile(&[], &[]).sections(), Section::all());
+        let repeated = profile(&[Section::Skills, Section::Education, Section::Skills], &[]);
+        assert_eq!(repeated.sections(), [Section::Skills, Section::Education]);
+    }
+
+    #[test]
+    fn lists_tags_in_first_seen_order() {
+        assert_eq!(tags_in_use(&resume()), ["backend", "frontend"]);
+    }
+
+    #[test]
+    fn slugs_are_safe_file_names() {
+        assert_eq!(slug("Back End/ML"), "back-end-ml");
+        assert_eq!(slug("Back-End"), slug("back end"));
+    }
+}
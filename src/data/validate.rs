@@ -0,0 +1,474 @@
+//! Checks a `Resume` for problems that would show up in the exported
+//! document: missing required fields, empty bullets, bad URLs, duplicates
+//! and dates that could not be parsed.
+
+use crate::data::date::DateRange;
+use crate::data::manager::{Point, Resume};
+use crate::data::profile;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// One problem, located by a path such as `experience[2].points[0]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub severity: Severity,
+    pub path: String,
+    pub message: String,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity.name(), self.path, self.message)
+    }
+}
+
+impl Issue {
+    /// The top-level section and entry index the path points into, e.g.
+    /// `("experience", Some(2))` for `experience[2].points[0]`.
+    pub fn location(&self) -> (&str, Option<usize>) {
+        let head = self.path.split('.').next().unwrap_or("");
+        match head.split_once('[') {
+            Some((section, rest)) => (section, rest.trim_end_matches(']').parse().ok()),
+            None => (head, None),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Report {
+    issues: Vec<Issue>,
+}
+
+impl Report {
+    fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Error, path.into(), message.into());
+    }
+
+    fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Warning, path.into(), message.into());
+    }
+
+    fn push(&mut self, severity: Severity, path: String, message: String) {
+        self.issues.push(Issue { severity, path, message });
+    }
+
+    fn required(&mut self, path: String, value: &str) {
+        if value.trim().is_empty() {
+            self.error(path, "is required");
+        }
+    }
+
+    fn url(&mut self, path: String, url: &str) {
+        if let Err(problem) = check_url(url) {
+            self.error(path, format!("'{}' is not a valid URL: {}", url, problem));
+        }
+    }
+
+    fn dates(&mut self, path: String, dates: &DateRange) {
+        match (&dates.start, &dates.end, &dates.text) {
+            (_, _, Some(text)) => self.warning(path, format!("could not parse '{}'; it is exported as written", text)),
+            (None, None, None) => self.warning(path, "no dates given"),
+            _ if dates.is_reversed() => self.error(path, "ends before it starts"),
+            _ => {}
+        }
+    }
+
+    fn points(&mut self, path: &str, points: &[Point]) {
+        let mut seen = HashMap::new();
+        for (i, point) in points.iter().enumerate() {
+            let point_path = format!("{}.points[{}]", path, i);
+            let text = point.text.trim();
+            if text.is_empty() {
+                self.warning(point_path, "bullet point is empty");
+            } else if let first = *seen.entry(text.to_lowercase()).or_insert(i)
+                && first != i
+            {
+                self.warning(point_path, format!("duplicates points[{}]", first));
+            }
+        }
+    }
+
+    /// Warns about entries whose `key` matches an earlier one in `section`.
+    fn duplicates(&mut self, section: &str, keys: impl Iterator<Item = String>) {
+        let mut seen = HashMap::new();
+        for (i, key) in keys.enumerate() {
+            if key.trim().is_empty() {
+                continue;
+            }
+            if let first = *seen.entry(key.to_lowercase()).or_insert(i)
+                && first != i
+            {
+                self.warning(format!("{}[{}]", section, i), format!("duplicates {}[{}]", section, first));
+            }
+        }
+    }
+}
+
+/// Runs every check over `resume`. Errors come before warnings; within a
+/// severity, issues are in document order.
+pub fn validate(resume: &Resume) -> Vec<Issue> {
+    let mut report = Report::default();
+
+    report.required("name".to_string(), &resume.name);
+    let contact = &resume.contact;
+    if contact.email.trim().is_empty() {
+        report.warning("contact.email", "no email address");
+    } else if !contact.email.contains('@') || contact.email.contains(char::is_whitespace) {
+        report.error("contact.email", format!("'{}' is not an email address", contact.email));
+    }
+    for (i, profile) in contact.profiles.iter().enumerate() {
+        let path = format!("contact.profiles[{}]", i);
+        report.required(format!("{}.label", path), &profile.label);
+        if profile.url.trim().is_empty() {
+            report.required(format!("{}.url", path), &profile.url);
+        } else {
+            report.url(format!("{}.url", path), &profile.url);
+        }
+    }
+    // An empty website or link is treated as absent by the templates.
+    if let Some(website) = resume.website.as_ref().filter(|w| !w.trim().is_empty()) {
+        report.url("website".to_string(), website);
+    }
+
+    for (i, education) in resume.education.iter().enumerate() {
+        let path = format!("education[{}]", i);
+        report.required(format!("{}.name", path), &education.name);
+        report.required(format!("{}.degree", path), &education.degree);
+        report.dates(format!("{}.dates", path), &education.dates);
+        report.points(&path, &education.points);
+    }
+    report.duplicates(
+        "education",
+        resume.education.iter().map(|e| format!("{}\n{}", e.name, e.degree)),
+    );
+
+    for (i, experience) in resume.experience.iter().enumerate() {
+        let path = format!("experience[{}]", i);
+        report.required(format!("{}.company", path), &experience.company);
+        report.required(format!("{}.position", path), &experience.position);
+        report.dates(format!("{}.dates", path), &experience.dates);
+        report.points(&path, &experience.points);
+        if experience.points.is_empty() {
+            report.warning(format!("{}.points", path), "no bullet points");
+        }
+    }
+    report.duplicates(
+        "experience",
+        resume
+            .experience
+            .iter()
+            .map(|e| format!("{}\n{}\n{}", e.company, e.position, e.dates)),
+    );
+
+    for (i, project) in resume.projects.iter().enumerate() {
+        let path = format!("projects[{}]", i);
+        report.required(format!("{}.name", path), &project.name);
+        if let Some(link) = project.link.as_ref().filter(|l| !l.trim().is_empty()) {
+            report.url(format!("{}.link", path), link);
+        }
+        report.points(&path, &project.points);
+    }
+    report.duplicates("projects", resume.projects.iter().map(|p| p.name.clone()));
+
+    for (i, category) in resume.skills.iter().enumerate() {
+        let path = format!("skills[{}]", i);
+        report.required(format!("{}.name", path), &category.name);
+        if category.items.iter().all(|s| s.name.trim().is_empty()) {
+            report.warning(format!("{}.items", path), "no skills listed");
+        }
+        let mut seen = HashMap::new();
+        for (j, skill) in category.items.iter().enumerate() {
+            let item_path = format!("{}.items[{}]", path, j);
+            if skill.name.trim().is_empty() {
+                if category.items.len() > 1 {
+                    report.warning(item_path, "skill name is empty");
+                }
+            } else if let first = *seen.entry(skill.name.trim().to_lowercase()).or_insert(j)
+                && first != j
+            {
+                report.warning(item_path, format!("duplicates items[{}]", first));
+            }
+        }
+    }
+    report.duplicates("skills", resume.skills.iter().map(|c| c.name.clone()));
+
+    let tags = profile::tags_in_use(resume);
+    for (i, variant) in resume.profiles.iter().enumerate() {
+        let path = format!("profiles[{}]", i);
+        report.required(format!("{}.name", path), &variant.name);
+        if variant.sections().len() < variant.sections.len() {
+            report.warning(format!("{}.sections", path), "lists a section more than once");
+        }
+        for tag in &variant.tags {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                report.warning(format!("{}.tags", path), format!("no entry or bullet point is tagged '{}'", tag));
+            }
+        }
+    }
+    // Profiles are exported to directories named after them, so names that
+    // only differ in case or punctuation would overwrite each other.
+    let mut folders = HashMap::new();
+    for (i, variant) in resume.profiles.iter().enumerate() {
+        if variant.name.trim().is_empty() {
+            continue;
+        }
+        let first = *folders.entry(profile::slug(&variant.name)).or_insert(i);
+        if first != i {
+            report.error(format!("profiles[{}].name", i), format!("exports to the same files as profiles[{}]", first));
+        }
+    }
+
+    let mut issues = report.issues;
+    // Stable, so document order is kept within each severity.
+    issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity));
+    issues
+}
+
+fn check_url(url: &str) -> Result<(), &'static str> {
+    let url = url.trim();
+    if url.contains(char::is_whitespace) {
+        return Err("it contains spaces");
+    }
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Err("it is missing http:// or https://");
+    };
+    if !matches!(scheme.to_lowercase().as_str(), "http" | "https") {
+        return Err("only http and https links are supported");
+    }
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = host.rsplit('@').next().unwrap_or(host);
+    let host = host.split(':').next().unwrap_or(host);
+    if host.is_empty() || !host.contains('.') || host.starts_with('.') || host.ends_with('.') {
+        return Err("the host name is missing or incomplete");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::manager::Education;
+    use crate::data::profile::ResumeProfile;
+
+    fn date_issues(dates: &str) -> Vec<Issue> {
+        let resume = Resume {
+            name: "Ada".to_string(),
+            education: vec![Education {
+                name: "UT Dallas".to_string(),
+                degree: "BS".to_string(),
+                dates: DateRange::parse(dates),
+                points: Vec::new(),
+                tags: Vec::new(),
+            }],
+            ..Resume::default()
+        };
+        validate(&resume)
+            .into_iter()
+            .filter(|issue| issue.path == "education[0].dates")
+            .collect()
+    }
+
+    #[test]
+    fn same_year_ranges_are_in_order() {
+        assert!(date_issues("2022 - 2022").is_empty());
+        assert!(date_issues("Jan 2022 - Dec 2022").is_empty());
+        assert!(date_issues("May 2022 - May 2022").is_empty());
+    }
+
+    #[test]
+    fn mixed_precision_ranges_are_in_order() {
+        assert!(date_issues("2022-05 - 2022").is_empty());
+        assert!(date_issues("2022 - 2022-03").is_empty());
+        assert!(date_issues("2021 - Feb 2022").is_empty());
+        assert!(date_issues("Aug 2021 - Present").is_empty());
+    }
+
+    #[test]
+    fn profiles_sharing_an_output_directory_are_errors() {
+        let named = |name: &str| ResumeProfile {
+            name: name.to_string(),
+            ..ResumeProfile::default()
+        };
+        let resume = Resume {
+            profiles: vec![named("back end"), named("frontend"), named("Back-End")],
+            ..Resume::default()
+        };
+        let issues: Vec<Issue> = validate(&resume).into_iter().filter(|i| i.path.starts_with("profiles")).collect();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "profiles[2].name");
+        assert_eq!(issues[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn reversed_ranges_are_errors() {
+        for dates in ["2023 - 2022", "2023 - 2022-12", "2022-06 - 2021", "Jun 2022 - May 2022"] {
+            let issues = date_issues(dates);
+            assert_eq!(issues.len(), 1, "{}", dates);
+            assert_eq!(issues[0].severity, Severity::Error);
+            assert_eq!(issues[0].message, "ends before it starts");
+        }
+    }
+
+    /// A resume with nothing to complain about.
+    fn clean() -> Resume {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": 2,
+            "name": "Ada Lovelace",
+            "contact": { "email": "ada@example.com", "phone": "", "location": "London",
+                         "profiles": [{ "label": "GitHub", "url": "https://github.com/ada" }] },
+            "education": [{ "name": "UT Dallas", "degree": "BS", "dates": { "start": "2018", "end": "2022" }, "points": [] }],
+            "experience": [{ "company": "Acme", "position": "Engineer", "dates": { "start": "2022-01", "end": "present" },
+                             "points": ["Shipped things"] }],
+            "projects": [{ "name": "resume-utd", "link": "https://example.com/resume", "points": [] }],
+            "skills": [{ "name": "Languages", "items": [{ "name": "Rust", "proficiency": null }] }]
+        }))
+        .unwrap()
+    }
+
+    fn paths(issues: &[Issue]) -> Vec<(Severity, &str)> {
+        issues.iter().map(|i| (i.severity, i.path.as_str())).collect()
+    }
+
+    #[test]
+    fn clean_resume_has_no_issues() {
+        assert_eq!(validate(&clean()), []);
+    }
+
+    #[test]
+    fn missing_required_fields_are_errors() {
+        let mut resume = clean();
+        resume.name = " ".to_string();
+        resume.experience[0].position.clear();
+        resume.skills[0].name.clear();
+        assert_eq!(
+            paths(&validate(&resume)),
+            [
+                (Severity::Error, "name"),
+                (Severity::Error, "experience[0].position"),
+                (Severity::Error, "skills[0].name"),
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_email_and_urls() {
+        let mut resume = clean();
+        resume.contact.email = "ada at example.com".to_string();
+        resume.contact.profiles[0].url = "github.com/ada".to_string();
+        resume.website = Some("ftp://ada.dev".to_string());
+        resume.projects[0].link = Some("https://localhost/x".to_string());
+        let issues = validate(&resume);
+        assert_eq!(
+            paths(&issues),
+            [
+                (Severity::Error, "contact.email"),
+                (Severity::Error, "contact.profiles[0].url"),
+                (Severity::Error, "website"),
+                (Severity::Error, "projects[0].link"),
+            ]
+        );
+        assert!(issues[1].message.ends_with("it is missing http:// or https://"));
+
+        assert_eq!(check_url("https://user@example.com:8080/path?q#f"), Ok(()));
+        assert_eq!(check_url("https://exa mple.com"), Err("it contains spaces"));
+
+        resume.contact.email.clear();
+        assert_eq!(validate(&resume).last().map(|i| i.severity), Some(Severity::Warning));
+    }
+
+    #[test]
+    fn empty_and_duplicate_content_are_warnings() {
+        let mut resume = clean();
+        resume.experience[0].points.push(Point::from("shipped things"));
+        resume.experience[0].points.push(Point::from(" "));
+        let project = resume.projects[0].clone();
+        resume.projects.push(project);
+        let skill = resume.skills[0].items[0].clone();
+        resume.skills[0].items.push(skill);
+        assert_eq!(
+            paths(&validate(&resume)),
+            [
+                (Severity::Warning, "experience[0].points[1]"),
+                (Severity::Warning, "experience[0].points[2]"),
+                (Severity::Warning, "projects[1]"),
+                (Severity::Warning, "skills[0].items[1]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn every_duplicate_points_at_the_first_copy() {
+        let mut resume = clean();
+        resume.experience[0].points.push(Point::from("shipped things"));
+        resume.experience[0].points.push(Point::from("Shipped Things"));
+        let project = resume.projects[0].clone();
+        resume.projects.push(project.clone());
+        resume.projects.push(project);
+        let skill = resume.skills[0].items[0].clone();
+        resume.skills[0].items.push(skill.clone());
+        resume.skills[0].items.push(skill);
+        let messages: Vec<(String, String)> = validate(&resume).into_iter().map(|i| (i.path, i.message)).collect();
+        let expected = [
+            ("experience[0].points[1]", "duplicates points[0]"),
+            ("experience[0].points[2]", "duplicates points[0]"),
+            ("projects[1]", "duplicates projects[0]"),
+            ("projects[2]", "duplicates projects[0]"),
+            ("skills[0].items[1]", "duplicates items[0]"),
+            ("skills[0].items[2]", "duplicates items[0]"),
+        ];
+        assert_eq!(messages, expected.map(|(path, message)| (path.to_string(), message.to_string())));
+    }
+
+    #[test]
+    fn unparsed_dates_and_unknown_profile_tags_are_warnings() {
+        let mut resume = clean();
+        resume.education[0].dates = DateRange::parse("someday");
+        resume.profiles.push(ResumeProfile {
+            name: "backend".to_string(),
+            tags: vec!["backend".to_string()],
+            ..ResumeProfile::default()
+        });
+        let issues = validate(&resume);
+        assert_eq!(
+            paths(&issues),
+            [(Severity::Warning, "education[0].dates"), (Severity::Warning, "profiles[0].tags")]
+        );
+    }
+
+    #[test]
+    fn errors_come_before_warnings() {
+        let mut resume = clean();
+        resume.experience[0].points.clear();
+        resume.projects[0].name.clear();
+        let severities: Vec<Severity> = validate(&resume).iter().map(|i| i.severity).collect();
+        assert_eq!(severities, [Severity::Error, Severity::Warning]);
+    }
+
+    #[test]
+    fn issue_location_names_the_entry() {
+        let issue = |path: &str| Issue {
+            severity: Severity::Warning,
+            path: path.to_string(),
+            message: String::new(),
+        };
+        assert_eq!(issue("experience[2].points[0]").location(), ("experience", Some(2)));
+        assert_eq!(issue("contact.email").location(), ("contact", None));
+        assert_eq!(issue("name").location(), ("name", None));
+    }
+}
+
@@ -0,0 +1,358 @@
+//! Renders every profile × format combination in one run, into
+//! `<root>/<profile>/resume.<ext>`.
+//!
+//! Each target's data and template are hashed and the hashes kept in
+//! `<root>/.export-hashes.json`; a target whose hash is unchanged and whose
+//! output still exists is skipped.
+
+use crate::data::manager::Resume;
+use crate::data::profile::{self, ResumeProfile};
+use crate::export::pdf::Engine;
+use crate::export::{Destination, Document, ExportError, Exporter, Format, TemplateSource};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST: &str = ".export-hashes.json";
+
+/// Directory used for the whole resume when the data defines no profiles.
+pub const ALL_PROFILE_DIR: &str = "all";
+
+/// Exports a resume to many targets at once.
+pub struct BatchExporter {
+    root: PathBuf,
+    formats: Vec<Format>,
+    pdf_engine: Option<Engine>,
+    force: bool,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Written { bytes: usize, pdf: Option<PathBuf>, warnings: Vec<String> },
+    Unchanged,
+    Failed(ExportError),
+}
+
+/// What happened to one profile × format target.
+#[derive(Debug)]
+pub struct Target {
+    /// `None` when the data defines no profiles and the whole resume is used.
+    pub profile: Option<String>,
+    pub format: Format,
+    pub path: PathBuf,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    pub targets: Vec<Target>,
+    /// Set when the hash manifest could not be written.
+    pub manifest_error: Option<ExportError>,
+}
+
+impl BatchExporter {
+    /// Every format, written under `root`, without PDFs.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            formats: Format::all(),
+            pdf_engine: None,
+            force: false,
+        }
+    }
+
+    pub fn with_formats(mut self, formats: Vec<Format>) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Also compile each LaTeX target to PDF with `engine`.
+    pub fn with_pdf(mut self, engine: Option<Engine>) -> Self {
+        self.pdf_engine = engine;
+        self
+    }
+
+    /// Re-render every target even if its hash is unchanged.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Exports every configured profile of `resume` in every format. A failed
+    /// target does not stop the others. A profile whose name maps to the same
+    /// directory as an earlier one fails rather than overwrite its output.
+    pub fn run(&self, resume: &Resume) -> BatchReport {
+        let manifest_path = self.root.join(MANIFEST);
+        let previous = read_manifest(&manifest_path);
+        let mut manifest = BTreeMap::new();
+        let mut report = BatchReport::default();
+        let mut claimed: HashMap<String, &str> = HashMap::new();
+
+        let profiles: Vec<Option<&ResumeProfile>> = if resume.profiles.is_empty() {
+            vec![None]
+        } else {
+            resume.profiles.iter().map(Some).collect()
+        };
+
+        for profile in profiles {
+            let dir = profile.map_or_else(|| ALL_PROFILE_DIR.to_string(), |p| profile::slug(&p.name));
+            if let Some(p) = profile
+                && let Some(&other) = claimed.get(&dir)
+            {
+                for &format in &self.formats {
+                    report.targets.push(Target {
+                        profile: Some(p.name.clone()),
+                        format,
+                        path: self.root.join(format!("{}/resume.{}", dir, format.extension())),
+                        outcome: Outcome::Failed(ExportError::Conflict {
+                            profile: p.name.clone(),
+                            other: other.to_string(),
+                        }),
+                    });
+                }
+                continue;
+            }
+            if let Some(p) = profile {
+                claimed.insert(dir.clone(), &p.name);
+            }
+            for &format in &self.formats {
+                let key = format!("{}/resume.{}", dir, format.extension());
+                let path = self.root.join(&key);
+                let pdf = self.pdf_engine.filter(|_| format == Format::Latex);
+
+                let outcome = match fingerprint(resume, profile, format, pdf) {
+                    Err(e) => Outcome::Failed(e),
+                    Ok(hash) => {
+                        let up_to_date = previous.get(&key) == Some(&hash)
+                            && path.exists()
+                            && (pdf.is_none() || path.with_extension("pdf").exists());
+                        let outcome = if up_to_date && !self.force {
+                            Outcome::Unchanged
+                        } else {
+                            export_target(resume, profile, format, &path, pdf)
+                        };
+                        if !matches!(outcome, Outcome::Failed(_)) {
+                            manifest.insert(key, hash);
+                        }
+                        outcome
+                    }
+                };
+
+                report.targets.push(Target {
+                    profile: profile.map(|p| p.name.clone()),
+                    format,
+                    path,
+                    outcome,
+                });
+            }
+        }
+
+        // Without a manifest the next run simply re-renders everything.
+        report.manifest_error = write_manifest(&manifest_path, &manifest).err();
+        report
+    }
+}
+
+/// Hash of everything that determines a target's output: this build, the
+/// format, the profile's view of the data, the template and the PDF engine.
+fn fingerprint(
+    resume: &Resume,
+    profile: Option<&ResumeProfile>,
+    format: Format,
+    pdf: Option<Engine>,
+) -> Result<String, ExportError> {
+    let document = Document::new(resume, profile);
+    let data = serde_json::to_string(&document).map_err(|e| ExportError::Render(e.to_string()))?;
+    let template = if format.has_template() {
+        TemplateSource::default_for(format).load()?
+    } else {
+        String::new()
+    };
+
+    let mut hash = Fnv1a::default();
+    let build = env!("CARGO_PKG_VERSION");
+    for part in [build, format.name(), &data, &template, pdf.map_or("", |e| e.name())] {
+        hash.write(part.as_bytes());
+        hash.write(&[0]);
+    }
+    Ok(format!("{:016x}", hash.0))
+}
+
+fn export_target(
+    resume: &Resume,
+    profile: Option<&ResumeProfile>,
+    format: Format,
+    path: &Path,
+    pdf: Option<Engine>,
+) -> Outcome {
+    let result = Exporter::new(format)
+        .with_destination(Destination::File(path.to_path_buf()))
+        .with_profile(profile.cloned())
+        .with_pdf(pdf)
+        .export(resume);
+    match result {
+        Ok(result) => Outcome::Written {
+            bytes: result.bytes,
+            pdf: result.pdf.map(|pdf| pdf.pdf),
+            warnings: result.warnings,
+        },
+        Err(e) => Outcome::Failed(e),
+    }
+}
+
+fn read_manifest(path: &Path) -> BTreeMap<String, String> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn write_manifest(path: &Path, manifest: &BTreeMap<String, String>) -> Result<(), ExportError> {
+    let text = serde_json::to_string_pretty(manifest).map_err(|e| ExportError::Render(e.to_string()))?;
+    super::write_file(path, &text)
+}
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher` its output is stable across Rust
+/// releases, so the manifest stays valid after a toolchain upgrade.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+impl BatchReport {
+    pub fn written(&self) -> usize {
+        self.count(|o| matches!(o, Outcome::Written { .. }))
+    }
+
+    pub fn unchanged(&self) -> usize {
+        self.count(|o| matches!(o, Outcome::Unchanged))
+    }
+
+    pub fn failed(&self) -> usize {
+        self.count(|o| matches!(o, Outcome::Failed(_)))
+    }
+
+    fn count(&self, predicate: impl Fn(&Outcome) -> bool) -> usize {
+        self.targets.iter().filter(|t| predicate(&t.outcome)).count()
+    }
+
+    /// "3 written, 7 unchanged, 1 failed"
+    pub fn summary(&self) -> String {
+        format!("{} written, {} unchanged, {} failed", self.written(), self.unchanged(), self.failed())
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.outcome {
+            Outcome::Written { bytes, pdf, .. } => {
+                write!(f, "wrote     {} ({} bytes)", self.path.display(), bytes)?;
+                if let Some(pdf) = pdf {
+                    write!(f, " and {}", pdf.display())?;
+                }
+                Ok(())
+            }
+            Outcome::Unchanged => write!(f, "unchanged {}", self.path.display()),
+            Outcome::Failed(e) => write!(f, "failed    {}: {}", self.path.display(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> ResumeProfile {
+        ResumeProfile {
+            name: name.to_string(),
+            ..ResumeProfile::default()
+        }
+    }
+
+    fn resume(profiles: &[&str]) -> Resume {
+        Resume {
+            name: "Ada Lovelace".to_string(),
+            profiles: profiles.iter().map(|name| named(name)).collect(),
+            ..Resume::default()
+        }
+    }
+
+    fn exporter(root: &Path) -> BatchExporter {
+        BatchExporter::new(root).with_formats(vec![Format::Markdown, Format::JsonResume])
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("resume-utd-batch-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn unchanged_targets_are_skipped() {
+        let root = temp_root("skip");
+        let mut resume = resume(&["backend", "frontend"]);
+
+        let first = exporter(&root).run(&resume);
+        assert_eq!((first.written(), first.unchanged(), first.failed()), (4, 0, 0));
+        assert!(root.join("backend/resume.md").exists());
+        assert!(root.join(MANIFEST).exists());
+
+        let second = exporter(&root).run(&resume);
+        assert_eq!((second.written(), second.unchanged()), (0, 4));
+
+        // A deleted output is written again even though its hash matches.
+        fs::remove_file(root.join("frontend/resume.md")).unwrap();
+        let third = exporter(&root).run(&resume);
+        assert_eq!((third.written(), third.unchanged()), (1, 3));
+
+        resume.name = "Ada King".to_string();
+        let fourth = exporter(&root).run(&resume);
+        assert_eq!((fourth.written(), fourth.unchanged()), (4, 0));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn force_rewrites_unchanged_targets() {
+        let root = temp_root("force");
+        let resume = resume(&[]);
+        exporter(&root).run(&resume);
+        assert!(root.join(ALL_PROFILE_DIR).join("resume.json").exists());
+
+        let forced = exporter(&root).with_force(true).run(&resume);
+        assert_eq!((forced.written(), forced.unchanged()), (2, 0));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn profiles_sharing_a_directory_fail_instead_of_overwriting() {
+        let root = temp_root("conflict");
+        let report = exporter(&root).run(&resume(&["Back End", "back-end"]));
+        assert_eq!((report.written(), report.failed()), (2, 2));
+        for target in report.targets.iter().filter(|t| t.profile.as_deref() == Some("back-end")) {
+            match &target.outcome {
+                Outcome::Failed(ExportError::Conflict { profile, other }) => {
+                    assert_eq!((profile.as_str(), other.as_str()), ("back-end", "Back End"));
+                }
+                other => panic!("expected a conflict, got {:?}", other),
+            }
+        }
+        let json = fs::read_to_string(root.join("back-end/resume.json")).unwrap();
+        assert!(json.contains("Ada Lovelace"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
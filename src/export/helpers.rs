@@ -0,0 +1,83 @@
+use crate::data::date::{DateRange, DateStyle};
+use crate::export::ExportError;
+use crate::data::manager::Skill;
+use handlebars::{
+    Context, Handlebars, Helper, HelperResult, Output, RenderContext, TemplateError,
+    handlebars_helper,
+};
+
+/// A registry with the helpers every format shares.
+pub fn registry() -> Handlebars<'static> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("date_range", Box::new(date_range_helper));
+    handlebars.register_helper("raw", Box::new(raw_helper));
+    handlebars.register_helper("skill_list", Box::new(skill_list));
+    handlebars.register_helper("section", Box::new(section));
+    handlebars
+}
+
+pub fn template_error(e: TemplateError) -> ExportError {
+    ExportError::Template {
+        source: String::new(),
+        message: e.to_string(),
+    }
+}
+
+/// `{{date_range dates style="year" separator="--"}}`; style is one of
+/// "month" (default), "year" or "numeric".
+fn date_range_helper(
+    h: &Helper<'_>,
+    r: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    if let Some(param) = h.param(0) {
+        let range: DateRange = serde_json::from_value(param.value().clone()).unwrap_or_default();
+        let style = h
+            .hash_get("style")
+            .and_then(|v| v.value().as_str())
+            .and_then(DateStyle::from_name)
+            .unwrap_or_default();
+        let separator = h
+            .hash_get("separator")
+            .and_then(|v| v.value().as_str())
+            .unwrap_or(style.default_separator());
+        out.write(&r.get_escape_fn()(&range.format(style, separator)))?;
+    }
+    Ok(())
+}
+
+/// `{{raw value}}` writes `value` without the format's escaping, for
+/// deliberate markup stored in the resume data.
+fn raw_helper(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    if let Some(param) = h.param(0) {
+        match param.value().as_str() {
+            Some(text) => out.write(text)?,
+            None => out.write(&param.value().to_string())?,
+        }
+    }
+    Ok(())
+}
+
+// `{{skill_list items}}` -> "Rust (Advanced), Python"
+handlebars_helper!(skill_list: |items: Vec<Skill>| {
+    items
+        .iter()
+        .map(|skill| match &skill.proficiency {
+            Some(level) => format!("{} ({})", skill.name, level),
+            None => skill.name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+});
+
+// `{{#each sections}}{{> (section this) ../this}}{{/each}}` renders the inline
+// partial named after each section, in the order the profile lists them.
+handlebars_helper!(section: |name: String| name);
@@ -0,0 +1,112 @@
+use crate::export::helpers;
+use crate::export::{Document, ExportError, Format, Renderer};
+use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext, html_escape};
+
+/// Renders a single self-contained HTML page. Values are HTML-escaped by
+/// Handlebars' default escaping.
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn format(&self) -> Format {
+        Format::Html
+    }
+
+    fn render(&self, document: &Document, template: &str) -> Result<String, ExportError> {
+        let mut handlebars = helpers::registry();
+        handlebars.register_escape_fn(html_escape);
+        handlebars.register_helper("link", Box::new(link_helper));
+        handlebars
+            .register_template_string("resume", template)
+            .map_err(helpers::template_error)?;
+
+        handlebars
+            .render("resume", document)
+            .map_err(|e| ExportError::Render(e.to_string()))
+    }
+}
+
+/// `{{link url}}` or `{{link url "label"}}`; the label defaults to the URL
+/// without its scheme.
+fn link_helper(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    if let Some(param) = h.param(0) {
+        let url = param.value().as_str().unwrap_or("");
+        let label = h
+            .param(1)
+            .and_then(|p| p.value().as_str())
+            .filter(|label| !label.is_empty())
+            .unwrap_or_else(|| url.split_once("://").map_or(url, |(_, rest)| rest));
+        out.write(&format!("<a href=\"{}\">{}</a>", html_escape(&encode_url(url)), html_escape(label)))?;
+    }
+    Ok(())
+}
+
+/// Percent-encodes the characters a URL cannot contain as typed: spaces,
+/// control characters, quotes, angle brackets and anything outside ASCII.
+/// Existing `%xx` escapes are kept as they are.
+pub fn encode_url(url: &str) -> String {
+    let mut encoded = String::with_capacity(url.len());
+    for c in url.chars() {
+        if c.is_ascii_graphic() && !matches!(c, '"' | '<' | '>' | '`') {
+            encoded.push(c);
+        } else {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::manager::Resume;
+
+    fn link(url: &str, label: Option<&str>) -> String {
+        let resume = Resume {
+            website: Some(url.to_string()),
+            name: label.unwrap_or_default().to_string(),
+            ..Resume::default()
+        };
+        let template = if label.is_some() { "{{link website name}}" } else { "{{link website}}" };
+        HtmlRenderer.render(&Document::new(&resume, None), template).unwrap()
+    }
+
+    #[test]
+    fn encodes_characters_urls_cannot_hold() {
+        assert_eq!(encode_url("https://example.com/my page"), "https://example.com/my%20page");
+        assert_eq!(encode_url("https://example.com/\"x\"<y>"), "https://example.com/%22x%22%3Cy%3E");
+        assert_eq!(encode_url("https://example.com/a\tb\n"), "https://example.com/a%09b%0A");
+        assert_eq!(encode_url("https://example.com/café"), "https://example.com/caf%C3%A9");
+        assert_eq!(encode_url("https://example.com/a%20b?q=1&r=2#top"), "https://example.com/a%20b?q=1&r=2#top");
+    }
+
+    #[test]
+    fn links_are_encoded_then_escaped() {
+        assert_eq!(
+            link("https://example.com/a b?q=1&r='2'", None),
+            "<a href=\"https://example.com/a%20b?q&#x3D;1&amp;r&#x3D;&#x27;2&#x27;\">example.com/a b?q&#x3D;1&amp;r&#x3D;&#x27;2&#x27;</a>"
+        );
+        assert_eq!(
+            link("https://ada.dev", Some("<Ada> & \"Co\"")),
+            "<a href=\"https://ada.dev\">&lt;Ada&gt; &amp; &quot;Co&quot;</a>"
+        );
+    }
+
+    #[test]
+    fn values_are_html_escaped() {
+        let resume = Resume {
+            name: "<script>alert('x')</script> & co".to_string(),
+            ..Resume::default()
+        };
+        let rendered = HtmlRenderer.render(&Document::new(&resume, None), "<h1>{{name}}</h1>").unwrap();
+        assert_eq!(rendered, "<h1>&lt;script&gt;alert(&#x27;x&#x27;)&lt;/script&gt; &amp; co</h1>");
+    }
+}
@@ -0,0 +1,27 @@
+use crate::data::json_resume;
+use crate::data::manager::Resume;
+use crate::export::{Document, ExportError, Format, Renderer};
+
+/// Writes a `resume.json` in the JSON Resume schema so any JSON Resume
+/// theme can render it. No Handlebars template is involved.
+pub struct JsonResumeRenderer;
+
+impl Renderer for JsonResumeRenderer {
+    fn format(&self) -> Format {
+        Format::JsonResume
+    }
+
+    fn render(&self, document: &Document, _template: &str) -> Result<String, ExportError> {
+        let (json, _) = json_resume::from_resume(&document.resume);
+        serde_json::to_string_pretty(&json).map_err(|e| ExportError::Render(e.to_string()))
+    }
+
+    fn warnings(&self, resume: &Resume) -> Vec<String> {
+        json_resume::from_resume(resume)
+            .1
+            .unmapped
+            .into_iter()
+            .map(|path| format!("{} has no JSON Resume equivalent", path))
+            .collect()
+    }
+}
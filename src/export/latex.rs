@@ -0,0 +1,159 @@
+use crate::export::helpers;
+use crate::export::{Document, ExportError, Format, Renderer};
+use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+
+/// Renders LaTeX. Every `{{value}}` is escaped with `escape_latex`; use
+/// `{{raw value}}` to insert deliberate LaTeX markup untouched.
+pub struct LatexRenderer;
+
+impl Renderer for LatexRenderer {
+    fn format(&self) -> Format {
+        Format::Latex
+    }
+
+    fn render(&self, document: &Document, template: &str) -> Result<String, ExportError> {
+        let mut handlebars = helpers::registry();
+        handlebars.register_escape_fn(escape_latex);
+        handlebars.register_helper("bold", Box::new(bold_helper));
+        handlebars.register_helper("href", Box::new(href_helper));
+        handlebars.register_helper("url", Box::new(url_helper));
+        handlebars
+            .register_template_string("resume", template)
+            .map_err(helpers::template_error)?;
+
+        handlebars
+            .render("resume", document)
+            .map_err(|e| ExportError::Render(e.to_string()))
+    }
+}
+
+/// Escapes characters that are special in LaTeX text mode.
+pub fn escape_latex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes a URL for use as the first argument of `\href` or `\url`.
+/// hyperref copes with most characters itself; `%` and `#` still need a
+/// backslash, and characters that would unbalance the argument are
+/// percent-encoded.
+pub fn escape_url(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            '%' | '#' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '{' => escaped.push_str("\\%7B"),
+            '}' => escaped.push_str("\\%7D"),
+            '\\' => escaped.push_str("\\%5C"),
+            '^' => escaped.push_str("\\%5E"),
+            ' ' => escaped.push_str("\\%20"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn bold_helper(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    if let Some(param) = h.param(0) {
+        let text = param.value().as_str().unwrap_or("");
+        out.write(&format!("\\textbf{{{}}}", escape_latex(text)))?;
+    }
+    Ok(())
+}
+
+/// `{{href url}}` or `{{href url "label"}}`.
+fn href_helper(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    if let Some(param) = h.param(0) {
+        let text = param.value().as_str().unwrap_or("");
+        let label = h
+            .param(1)
+            .and_then(|p| p.value().as_str())
+            .unwrap_or("GitHub Repo Link");
+        out.write(&format!("\\href{{{}}}{{{}}}", escape_url(text), escape_latex(label)))?;
+    }
+    Ok(())
+}
+
+fn url_helper(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    if let Some(param) = h.param(0) {
+        let text = param.value().as_str().unwrap_or("");
+        out.write(&format!("\\url{{{}}}", escape_url(text)))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::manager::Resume;
+
+    #[test]
+    fn escapes_every_special_character() {
+        assert_eq!(escape_latex("R&D"), "R\\&D");
+        assert_eq!(escape_latex("100%"), "100\\%");
+        assert_eq!(escape_latex("$5"), "\\$5");
+        assert_eq!(escape_latex("C#"), "C\\#");
+        assert_eq!(escape_latex("snake_case"), "snake\\_case");
+        assert_eq!(escape_latex("{x}"), "\\{x\\}");
+        assert_eq!(escape_latex("~/bin"), "\\textasciitilde{}/bin");
+        assert_eq!(escape_latex("x^2"), "x\\textasciicircum{}2");
+        assert_eq!(escape_latex("C:\\temp"), "C:\\textbackslash{}temp");
+        assert_eq!(escape_latex("Zoë – naïve"), "Zoë – naïve");
+    }
+
+    #[test]
+    fn escapes_urls_for_hyperref() {
+        assert_eq!(escape_url("https://example.com/a%20b#top"), "https://example.com/a\\%20b\\#top");
+        assert_eq!(escape_url("https://example.com/my page"), "https://example.com/my\\%20page");
+        assert_eq!(escape_url("https://example.com/{x}"), "https://example.com/\\%7Bx\\%7D");
+        assert_eq!(escape_url("https://example.com/~ada?q=1&r=_"), "https://example.com/~ada?q=1&r=_");
+    }
+
+    #[test]
+    fn helpers_escape_their_arguments() {
+        let resume = Resume {
+            name: "Ada & Co".to_string(),
+            website: Some("https://ada.dev/#100%".to_string()),
+            ..Resume::default()
+        };
+        let template = "{{name}}|{{raw name}}|{{bold name}}|{{href website \"50% off\"}}|{{url website}}";
+        let rendered = LatexRenderer.render(&Document::new(&resume, None), template).unwrap();
+        assert_eq!(
+            rendered,
+            "Ada \\& Co|Ada & Co|\\textbf{Ada \\& Co}|\\href{https://ada.dev/\\#100\\%}{50\\% off}|\\url{https://ada.dev/\\#100\\%}"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::export::helpers;
+use crate::export::{Document, ExportError, Format, Renderer};
+use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
+
+/// Renders Markdown suitable for READMEs and GitHub profile pages. Every
+/// `{{value}}` is escaped with `escape_markdown`.
+pub struct MarkdownRenderer;
+
+impl Renderer for MarkdownRenderer {
+    fn format(&self) -> Format {
+        Format::Markdown
+    }
+
+    fn render(&self, document: &Document, template: &str) -> Result<String, ExportError> {
+        let mut handlebars = helpers::registry();
+        handlebars.register_escape_fn(escape_markdown);
+        handlebars.register_helper("link", Box::new(link_helper));
+        handlebars
+            .register_template_string("resume", template)
+            .map_err(helpers::template_error)?;
+
+        handlebars
+            .render("resume", document)
+            .map_err(|e| ExportError::Render(e.to_string()))
+    }
+}
+
+/// Backslash-escapes characters that would otherwise start inline markup:
+/// emphasis, code, links, strikethrough and math, plus a `#` that would
+/// turn the start of a line into a heading.
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut line_start = true;
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~' | '$') || (c == '#' && line_start) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        line_start = c == '\n' || (line_start && c.is_whitespace());
+    }
+    escaped
+}
+
+/// `{{link url}}` or `{{link url "label"}}`.
+fn link_helper(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    if let Some(param) = h.param(0) {
+        let url = param.value().as_str().unwrap_or("");
+        let label = h.param(1).and_then(|p| p.value().as_str()).unwrap_or(url);
+        let url = url.replace(' ', "%20").replace('(', "%28").replace(')', "%29");
+        out.write(&format!("[{}]({})", escape_markdown(label), url))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::manager::Resume;
+
+    #[test]
+    fn escapes_inline_markup() {
+        assert_eq!(escape_markdown("*bold* _em_ `code`"), "\\*bold\\* \\_em\\_ \\`code\\`");
+        assert_eq!(escape_markdown("[x](y) <b> a|b C:\\"), "\\[x\\](y) \\<b\\> a\\|b C:\\\\");
+        assert_eq!(escape_markdown("~~gone~~"), "\\~\\~gone\\~\\~");
+        assert_eq!(escape_markdown("$5 to $10"), "\\$5 to \\$10");
+    }
+
+    #[test]
+    fn escapes_hashes_only_where_they_start_a_heading() {
+        assert_eq!(escape_markdown("# Title"), "\\# Title");
+        assert_eq!(escape_markdown("  ## Title"), "  \\## Title");
+        assert_eq!(escape_markdown("one\n# two"), "one\n\\# two");
+        assert_eq!(escape_markdown("C# and F#"), "C# and F#");
+    }
+
+    #[test]
+    fn links_encode_characters_that_end_the_url() {
+        let resume = Resume {
+            website: Some("https://example.com/a (b)".to_string()),
+            ..Resume::default()
+        };
+        let rendered = MarkdownRenderer
+            .render(&Document::new(&resume, None), "{{link website \"my_site\"}}")
+            .unwrap();
+        assert_eq!(rendered, "[my\\_site](https://example.com/a%20%28b%29)");
+    }
+}
@@ -0,0 +1,401 @@
+pub mod batch;
+pub mod helpers;
+pub mod html;
+pub mod json_resume;
+pub mod latex;
+pub mod markdown;
+pub mod pdf;
+pub mod text;
+
+pub use html::HtmlRenderer;
+pub use json_resume::JsonResumeRenderer;
+pub use latex::LatexRenderer;
+pub use markdown::MarkdownRenderer;
+pub use text::TextRenderer;
+
+use crate::data::manager::Resume;
+use crate::data::profile::{self, ResumeProfile, Section};
+use pdf::{Diagnostic, Engine, PdfOutput};
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Output formats the export engine knows how to produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Latex,
+    Markdown,
+    Html,
+    Text,
+    /// The jsonresume.org schema, for use with JSON Resume themes.
+    JsonResume,
+}
+
+/// Where a renderer gets its Handlebars template from.
+#[derive(Debug, Clone)]
+pub enum TemplateSource {
+    File(PathBuf),
+    Inline(String),
+    /// The default template compiled into the binary.
+    Builtin(Format),
+}
+
+/// Where rendered output ends up.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Destination {
+    File(PathBuf),
+    Stdout,
+    /// Keep the output in `ExportResult::content` only.
+    Memory,
+}
+
+/// What a template is rendered against: the resume narrowed to a profile,
+/// plus `sections`, the order its sections should appear in.
+#[derive(Debug, Serialize)]
+pub struct Document {
+    #[serde(flatten)]
+    pub resume: Resume,
+    pub sections: Vec<Section>,
+    /// Name of the profile the document was built from, if any.
+    pub profile: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ExportResult {
+    pub format: Format,
+    pub destination: Destination,
+    pub bytes: usize,
+    /// The rendered document, populated for `Destination::Memory`.
+    pub content: Option<String>,
+    /// Set when PDF compilation was requested and succeeded.
+    pub pdf: Option<PdfOutput>,
+    /// Data the format could not represent, e.g. unmapped JSON Resume fields.
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Template { source: String, message: String },
+    Render(String),
+    Io { path: PathBuf, error: io::Error },
+    Compile { engine: Engine, diagnostics: Vec<Diagnostic>, log: Option<PathBuf> },
+    /// Two profiles would write the same files.
+    Conflict { profile: String, other: String },
+}
+
+/// Turns a `Document` plus template text into output of one format.
+pub trait Renderer {
+    fn format(&self) -> Format;
+    fn render(&self, document: &Document, template: &str) -> Result<String, ExportError>;
+
+    /// Parts of `resume` that will be lost in this format.
+    fn warnings(&self, _resume: &Resume) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Ties a renderer to a template source and a destination.
+pub struct Exporter {
+    renderer: Box<dyn Renderer>,
+    template: TemplateSource,
+    destination: Destination,
+    pdf_engine: Option<Engine>,
+    profile: Option<ResumeProfile>,
+}
+
+impl Format {
+    pub fn all() -> Vec<Format> {
+        vec![Format::Latex, Format::Markdown, Format::Html, Format::Text, Format::JsonResume]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Latex => "latex",
+            Format::Markdown => "markdown",
+            Format::Html => "html",
+            Format::Text => "text",
+            Format::JsonResume => "json-resume",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Format::Latex => "LaTeX",
+            Format::Markdown => "Markdown",
+            Format::Html => "HTML",
+            Format::Text => "Plain text",
+            Format::JsonResume => "JSON Resume",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Format> {
+        Format::all().into_iter().find(|f| f.name() == name.to_lowercase())
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Latex => "tex",
+            Format::Markdown => "md",
+            Format::Html => "html",
+            Format::Text => "txt",
+            Format::JsonResume => "json",
+        }
+    }
+
+    pub fn renderer(&self) -> Box<dyn Renderer> {
+        match self {
+            Format::Latex => Box::new(LatexRenderer),
+            Format::Markdown => Box::new(MarkdownRenderer),
+            Format::Html => Box::new(HtmlRenderer),
+            Format::Text => Box::new(TextRenderer::default()),
+            Format::JsonResume => Box::new(JsonResumeRenderer),
+        }
+    }
+
+    /// Whether output is rendered through a Handlebars template.
+    pub fn has_template(&self) -> bool {
+        *self != Format::JsonResume
+    }
+
+    pub fn default_template(&self) -> PathBuf {
+        PathBuf::from(format!("templates/resume.{}.hbs", self.extension()))
+    }
+
+    pub fn builtin_template(&self) -> &'static str {
+        match self {
+            Format::Latex => include_str!("templates/resume.tex.hbs"),
+            Format::Markdown => include_str!("templates/resume.md.hbs"),
+            Format::Html => include_str!("templates/resume.html.hbs"),
+            Format::Text => include_str!("templates/resume.txt.hbs"),
+            Format::JsonResume => "",
+        }
+    }
+
+    pub fn next(&self) -> Format {
+        let all = Format::all();
+        let index = all.iter().position(|f| f == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    pub fn default_output(&self) -> PathBuf {
+        PathBuf::from(format!("output/resume.{}", self.extension()))
+    }
+
+    /// `default_output`, or `output/resume-<profile>.<ext>` so variants do
+    /// not overwrite each other.
+    pub fn default_output_for(&self, profile: Option<&str>) -> PathBuf {
+        match profile {
+            Some(profile) => PathBuf::from(format!("output/resume-{}.{}", profile::slug(profile), self.extension())),
+            None => self.default_output(),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl TemplateSource {
+    /// The on-disk default template for `format` if there is one, otherwise
+    /// the built-in template.
+    pub fn default_for(format: Format) -> Self {
+        let path = format.default_template();
+        if format.has_template() && path.exists() {
+            TemplateSource::File(path)
+        } else {
+            TemplateSource::Builtin(format)
+        }
+    }
+
+    pub fn load(&self) -> Result<String, ExportError> {
+        match self {
+            TemplateSource::File(path) => fs::read_to_string(path).map_err(|error| ExportError::Io {
+                path: path.clone(),
+                error,
+            }),
+            TemplateSource::Inline(text) => Ok(text.clone()),
+            TemplateSource::Builtin(format) => Ok(format.builtin_template().to_string()),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            TemplateSource::File(path) => path.display().to_string(),
+            TemplateSource::Inline(_) => "<inline template>".to_string(),
+            TemplateSource::Builtin(format) if !format.has_template() => "<none>".to_string(),
+            TemplateSource::Builtin(format) => format!("<built-in {} template>", format),
+        }
+    }
+}
+
+impl Document {
+    /// The part of `resume` that `profile` selects; `None` renders everything
+    /// in the default section order.
+    pub fn new(resume: &Resume, profile: Option<&ResumeProfile>) -> Self {
+        Self {
+            resume: profile::apply(resume, profile),
+            sections: profile.map_or_else(Section::all, |p| p.sections()),
+            profile: profile.map(|p| p.name.clone()),
+        }
+    }
+}
+
+impl fmt::Display for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Destination::File(path) => write!(f, "{}", path.display()),
+            Destination::Stdout => write!(f, "<stdout>"),
+            Destination::Memory => write!(f, "<memory>"),
+        }
+    }
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Template { source, message } => write!(f, "template {}: {}", source, message),
+            ExportError::Render(message) => write!(f, "render failed: {}", message),
+            ExportError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            ExportError::Compile { engine, diagnostics, log } => {
+                write!(f, "{} failed", engine)?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n  {}", diagnostic)?;
+                }
+                if let Some(log) = log {
+                    write!(f, "\n  (full log: {})", log.display())?;
+                }
+                Ok(())
+            }
+            ExportError::Conflict { profile, other } => write!(
+                f,
+                "profile '{}' would overwrite the output of '{}'; give one of them a different name",
+                profile, other
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl Exporter {
+    /// An exporter for `format` using its default template and output path.
+    pub fn new(format: Format) -> Self {
+        Self {
+            renderer: format.renderer(),
+            template: TemplateSource::default_for(format),
+            destination: Destination::File(format.default_output()),
+            pdf_engine: None,
+            profile: None,
+        }
+    }
+
+    /// Swap in a configured renderer, e.g. `TextRenderer::new(100)`.
+    pub fn with_renderer(mut self, renderer: Box<dyn Renderer>) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    pub fn with_template(mut self, template: TemplateSource) -> Self {
+        self.template = template;
+        self
+    }
+
+    pub fn with_destination(mut self, destination: Destination) -> Self {
+        self.destination = destination;
+        self
+    }
+
+    /// Also compile the output to PDF with `engine`. Only applies to LaTeX
+    /// written to a file; the PDF lands next to it.
+    pub fn with_pdf(mut self, engine: Option<Engine>) -> Self {
+        self.pdf_engine = engine;
+        self
+    }
+
+    /// Render only what `profile` selects, in its section order.
+    pub fn with_profile(mut self, profile: Option<ResumeProfile>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    pub fn template(&self) -> &TemplateSource {
+        &self.template
+    }
+
+    pub fn render(&self, resume: &Resume) -> Result<String, ExportError> {
+        self.render_document(&Document::new(resume, self.profile.as_ref()))
+    }
+
+    fn render_document(&self, document: &Document) -> Result<String, ExportError> {
+        let template = if self.renderer.format().has_template() {
+            self.template.load()?
+        } else {
+            String::new()
+        };
+        self.renderer.render(document, &template).map_err(|e| match e {
+            ExportError::Template { message, .. } => ExportError::Template {
+                source: self.template.describe(),
+                message,
+            },
+            other => other,
+        })
+    }
+
+    pub fn export(&self, resume: &Resume) -> Result<ExportResult, ExportError> {
+        let document = Document::new(resume, self.profile.as_ref());
+        let output = self.render_document(&document)?;
+        let bytes = output.len();
+
+        let mut pdf = None;
+        let content = match &self.destination {
+            Destination::File(path) => {
+                write_file(path, &output)?;
+                if let Some(engine) = self.pdf_engine
+                    && self.renderer.format() == Format::Latex
+                {
+                    pdf = Some(pdf::compile(path, engine)?);
+                }
+                None
+            }
+            Destination::Stdout => {
+                io::stdout()
+                    .write_all(output.as_bytes())
+                    .map_err(|error| ExportError::Io {
+                        path: PathBuf::from("<stdout>"),
+                        error,
+                    })?;
+                None
+            }
+            Destination::Memory => Some(output),
+        };
+
+        Ok(ExportResult {
+            format: self.renderer.format(),
+            destination: self.destination.clone(),
+            bytes,
+            content,
+            pdf,
+            warnings: self.renderer.warnings(&document.resume),
+        })
+    }
+}
+
+/// Writes the built-in template for `format` to `path` so it can be customized.
+pub fn write_builtin_template(format: Format, path: &Path) -> Result<(), ExportError> {
+    write_file(path, format.builtin_template())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), ExportError> {
+    let io_error = |error| ExportError::Io {
+        path: path.to_path_buf(),
+        error,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    fs::write(path, contents).map_err(io_error)
+}
@@ -0,0 +1,299 @@
+use crate::export::ExportError;
+use std::env;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A locally installed LaTeX engine used to turn the exported `.tex` into a PDF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Engine {
+    Xelatex,
+    Lualatex,
+    Pdflatex,
+    Tectonic,
+}
+
+/// One error pulled out of the engine's log.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub struct PdfOutput {
+    pub engine: Engine,
+    pub pdf: PathBuf,
+    pub log: PathBuf,
+}
+
+impl Engine {
+    pub fn all() -> Vec<Engine> {
+        vec![Engine::Xelatex, Engine::Lualatex, Engine::Pdflatex, Engine::Tectonic]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Engine::Xelatex => "xelatex",
+            Engine::Lualatex => "lualatex",
+            Engine::Pdflatex => "pdflatex",
+            Engine::Tectonic => "tectonic",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Engine> {
+        Engine::all().into_iter().find(|e| e.name() == name.to_lowercase())
+    }
+
+    /// The first engine found on `PATH`, preferring XeLaTeX like the Docker build.
+    pub fn detect() -> Option<Engine> {
+        Engine::all().into_iter().find(|e| e.is_installed())
+    }
+
+    pub fn is_installed(&self) -> bool {
+        env::var_os("PATH").is_some_and(|paths| self.is_in(&paths))
+    }
+
+    /// Whether the engine's executable is in one of the `PATH`-style `paths`.
+    fn is_in(&self, paths: &OsStr) -> bool {
+        env::split_paths(paths).any(|dir| {
+            let candidate = dir.join(self.name());
+            candidate.is_file() || candidate.with_extension("exe").is_file()
+        })
+    }
+
+    pub fn next(&self) -> Engine {
+        let all = Engine::all();
+        let index = all.iter().position(|e| e == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    fn command(&self, tex_file: &str, out_dir: &Path) -> Command {
+        let mut command = Command::new(self.name());
+        match self {
+            Engine::Tectonic => {
+                command.arg("--keep-logs").arg("--outdir").arg(out_dir);
+            }
+            _ => {
+                command
+                    .arg("-interaction=nonstopmode")
+                    .arg("-halt-on-error")
+                    .arg("-file-line-error")
+                    .arg(format!("-output-directory={}", out_dir.display()));
+            }
+        }
+        command.arg(tex_file).current_dir(out_dir);
+        command
+    }
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Compiles `tex_path` with `engine` in a scratch directory, then copies the
+/// PDF and log next to the `.tex` file.
+pub fn compile(tex_path: &Path, engine: Engine) -> Result<PdfOutput, ExportError> {
+    let work_dir = scratch_dir();
+    fs::create_dir_all(&work_dir).map_err(io_error(&work_dir))?;
+    let result = run_engine(tex_path, engine, &work_dir);
+    let _ = fs::remove_dir_all(&work_dir);
+    result
+}
+
+fn run_engine(tex_path: &Path, engine: Engine, work_dir: &Path) -> Result<PdfOutput, ExportError> {
+    fs::copy(tex_path, work_dir.join("resume.tex")).map_err(io_error(tex_path))?;
+
+    let output = match engine.command("resume.tex", work_dir).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(ExportError::Compile {
+                engine,
+                diagnostics: vec![Diagnostic {
+                    line: None,
+                    message: format!("{} is not installed or not on PATH", engine),
+                }],
+                log: None,
+            });
+        }
+        Err(e) => return Err(ExportError::Io { path: PathBuf::from(engine.name()), error: e }),
+    };
+
+    let log_text = fs::read_to_string(work_dir.join("resume.log")).unwrap_or_else(|_| {
+        format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    let log_path = tex_path.with_extension("log");
+    fs::write(&log_path, &log_text).map_err(io_error(&log_path))?;
+
+    let built_pdf = work_dir.join("resume.pdf");
+    if !output.status.success() || !built_pdf.exists() {
+        let mut diagnostics = parse_log(&log_text);
+        if diagnostics.is_empty() {
+            // Tectonic reports some failures only on stderr.
+            diagnostics = parse_log(&String::from_utf8_lossy(&output.stderr));
+        }
+        if diagnostics.is_empty() {
+            diagnostics.push(Diagnostic {
+                line: None,
+                message: format!("{} exited with {}", engine, output.status),
+            });
+        }
+        return Err(ExportError::Compile {
+            engine,
+            diagnostics,
+            log: Some(log_path),
+        });
+    }
+
+    let pdf_path = tex_path.with_extension("pdf");
+    fs::copy(&built_pdf, &pdf_path).map_err(io_error(&pdf_path))?;
+
+    Ok(PdfOutput {
+        engine,
+        pdf: pdf_path,
+        log: log_path,
+    })
+}
+
+/// Pulls errors out of a TeX log. Understands both `-file-line-error`
+/// output (`./resume.tex:42: Undefined control sequence.`) and the classic
+/// `! message` ... `l.42` form.
+pub fn parse_log(log: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut pending: Option<String> = None;
+
+    for line in log.lines() {
+        let line = line.trim_start_matches("error: ");
+        if let Some(diagnostic) = parse_file_line_error(line) {
+            diagnostics.push(diagnostic);
+        } else if let Some(message) = line.strip_prefix("! ") {
+            if let Some(previous) = pending.take() {
+                diagnostics.push(Diagnostic { line: None, message: previous });
+            }
+            pending = Some(message.trim().to_string());
+        } else if let Some(rest) = line.strip_prefix("l.")
+            && let Some(message) = pending.take()
+        {
+            let number = rest.split(|c: char| !c.is_ascii_digit()).next().unwrap_or("");
+            diagnostics.push(Diagnostic {
+                line: number.parse().ok(),
+                message,
+            });
+        }
+    }
+
+    if let Some(message) = pending {
+        diagnostics.push(Diagnostic { line: None, message });
+    }
+    diagnostics
+}
+
+fn parse_file_line_error(line: &str) -> Option<Diagnostic> {
+    let (file, rest) = line.split_once(".tex:")?;
+    if file.contains(' ') {
+        return None;
+    }
+    let (number, message) = rest.split_once(':')?;
+    Some(Diagnostic {
+        line: Some(number.trim().parse().ok()?),
+        message: message.trim().to_string(),
+    })
+}
+
+fn io_error(path: &Path) -> impl FnOnce(io::Error) -> ExportError {
+    let path = path.to_path_buf();
+    move |error| ExportError::Io { path, error }
+}
+
+fn scratch_dir() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    env::temp_dir().join(format!("resume-utd-{}-{}", std::process::id(), nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines_and_messages(log: &str) -> Vec<(Option<usize>, String)> {
+        parse_log(log).into_iter().map(|d| (d.line, d.message)).collect()
+    }
+
+    #[test]
+    fn reads_classic_errors_with_their_line() {
+        let log = r"This is XeTeX, Version 3.141592653-2.6-0.999995 (TeX Live 2023) (preloaded format=xelatex)
+(./resume.tex
+LaTeX2e <2023-06-01> patch level 1
+! Undefined control sequence.
+l.42 \resumeItm
+               {Shipped things}
+?
+! Emergency stop.
+<*> resume.tex
+
+No pages of output.
+";
+        assert_eq!(
+            lines_and_messages(log),
+            [(Some(42), "Undefined control sequence.".to_string()), (None, "Emergency stop.".to_string())]
+        );
+    }
+
+    #[test]
+    fn reads_file_line_errors() {
+        let log = "(./resume.tex\n./resume.tex:17: Missing $ inserted.\n<inserted text>\nerror: ./resume.tex:3: LaTeX Error: File `fontspec.sty' not found.\n";
+        assert_eq!(
+            lines_and_messages(log),
+            [
+                (Some(17), "Missing $ inserted.".to_string()),
+                (Some(3), "LaTeX Error: File `fontspec.sty' not found.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_clean_log_has_no_diagnostics() {
+        assert!(parse_log("Output written on resume.pdf (1 page).\nTranscript written on resume.log.\n").is_empty());
+    }
+
+    #[test]
+    fn finds_engines_on_the_path() {
+        let dir = std::env::temp_dir().join(format!("resume-utd-engines-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("lualatex"), "").unwrap();
+        let paths = env::join_paths([Path::new("/nonexistent"), &dir]).unwrap();
+        assert!(Engine::Lualatex.is_in(&paths));
+        assert!(!Engine::Xelatex.is_in(&paths));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn names_round_trip_and_cycle() {
+        for engine in Engine::all() {
+            assert_eq!(Engine::from_name(&engine.name().to_uppercase()), Some(engine));
+        }
+        assert_eq!(Engine::Tectonic.next(), Engine::Xelatex);
+        assert_eq!(Engine::from_name("latex"), None);
+    }
+}
@@ -0,0 +1,192 @@
+use crate::export::helpers;
+use crate::export::{Document, ExportError, Format, Renderer};
+use handlebars::{
+    Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, RenderError,
+    no_escape,
+};
+
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Renders plain, markup-free text for pasting into applicant tracking
+/// systems. Bullet points are word-wrapped to `width` columns.
+pub struct TextRenderer {
+    pub width: usize,
+}
+
+impl Default for TextRenderer {
+    fn default() -> Self {
+        Self { width: DEFAULT_WIDTH }
+    }
+}
+
+impl TextRenderer {
+    pub fn new(width: usize) -> Self {
+        Self { width }
+    }
+}
+
+impl Renderer for TextRenderer {
+    fn format(&self) -> Format {
+        Format::Text
+    }
+
+    fn render(&self, document: &Document, template: &str) -> Result<String, ExportError> {
+        let mut handlebars = helpers::registry();
+        handlebars.register_escape_fn(no_escape);
+        handlebars.register_helper("wrap", Box::new(WrapHelper { width: self.width }));
+        handlebars.register_helper("rule", Box::new(RuleHelper { width: self.width }));
+        handlebars.register_helper("upper", Box::new(upper_helper));
+        handlebars
+            .register_template_string("resume", template)
+            .map_err(helpers::template_error)?;
+
+        handlebars
+            .render("resume", document)
+            .map_err(|e| ExportError::Render(e.to_string()))
+    }
+}
+
+/// Greedily wraps `text` to `width` columns. The first line starts with
+/// `prefix`; continuation lines are indented by `indent`.
+pub fn wrap(text: &str, width: usize, prefix: &str, indent: &str) -> String {
+    let mut lines = Vec::new();
+    let mut line = prefix.to_string();
+    let mut line_len = prefix.chars().count();
+    let mut line_has_words = false;
+
+    for word in text.split_whitespace() {
+        let word_len = word.chars().count();
+        if line_has_words && line_len + 1 + word_len > width {
+            lines.push(line);
+            line = indent.to_string();
+            line_len = indent.chars().count();
+            line_has_words = false;
+        }
+        if line_has_words {
+            line.push(' ');
+            line_len += 1;
+        }
+        line.push_str(word);
+        line_len += word_len;
+        line_has_words = true;
+    }
+
+    lines.push(line);
+    lines.join("\n")
+}
+
+/// `{{wrap value ...}}` joins its positional params and wraps the result.
+/// `prefix` (e.g. `"- "`) starts the first line; `indent` defaults to as
+/// many spaces as the prefix is wide.
+struct WrapHelper {
+    width: usize,
+}
+
+impl HelperDef for WrapHelper {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> Result<(), RenderError> {
+        let text: String = h
+            .params()
+            .iter()
+            .map(|p| p.value().as_str().map_or_else(|| p.value().to_string(), str::to_string))
+            .collect();
+        let prefix = h.hash_get("prefix").and_then(|v| v.value().as_str()).unwrap_or("");
+        let indent = h
+            .hash_get("indent")
+            .and_then(|v| v.value().as_str())
+            .map_or_else(|| " ".repeat(prefix.chars().count()), str::to_string);
+
+        out.write(&wrap(&text, self.width, prefix, &indent))?;
+        Ok(())
+    }
+}
+
+/// `{{rule}}` draws a line of dashes across the full width.
+struct RuleHelper {
+    width: usize,
+}
+
+impl HelperDef for RuleHelper {
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        _: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> Result<(), RenderError> {
+        out.write(&"-".repeat(self.width))?;
+        Ok(())
+    }
+}
+
+fn upper_helper(
+    h: &Helper<'_>,
+    _: &Handlebars<'_>,
+    _: &Context,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    if let Some(param) = h.param(0) {
+        out.write(&param.value().as_str().unwrap_or("").to_uppercase())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::manager::Resume;
+
+    #[test]
+    fn wraps_at_word_boundaries() {
+        let text = "Built a caching layer that cut p99 latency by forty percent";
+        assert_eq!(
+            wrap(text, 24, "- ", "  "),
+            "- Built a caching layer\n  that cut p99 latency\n  by forty percent"
+        );
+    }
+
+    #[test]
+    fn every_line_fits_the_width() {
+        let text = "one two three four five six seven eight nine ten eleven twelve";
+        for width in 10..40 {
+            for line in wrap(text, width, "* ", "  ").lines() {
+                assert!(line.chars().count() <= width, "{:?} is over {}", line, width);
+            }
+        }
+    }
+
+    #[test]
+    fn long_words_get_a_line_of_their_own() {
+        assert_eq!(wrap("see https://example.com/a/long/path now", 12, "", ""), "see\nhttps://example.com/a/long/path\nnow");
+    }
+
+    #[test]
+    fn counts_characters_not_bytes() {
+        assert_eq!(wrap("café résumé naïve", 12, "", ""), "café résumé\nnaïve");
+    }
+
+    #[test]
+    fn collapses_whitespace_and_keeps_the_prefix_on_empty_text() {
+        assert_eq!(wrap("  a\n\tb  ", 80, "", ""), "a b");
+        assert_eq!(wrap("", 80, "- ", "  "), "- ");
+    }
+
+    #[test]
+    fn wrap_helper_indents_under_the_prefix() {
+        let resume = Resume {
+            name: "Ada Lovelace wrote the first published program".to_string(),
+            ..Resume::default()
+        };
+        let document = Document::new(&resume, None);
+        let output = TextRenderer::new(20).render(&document, r#"{{wrap name prefix="> "}}"#).unwrap();
+        assert_eq!(output, "> Ada Lovelace wrote\n  the first\n  published program");
+    }
+}
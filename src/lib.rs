@@ -0,0 +1,4 @@
+pub mod cli;
+pub mod data;
+pub mod export;
+pub mod tui;
@@ -1,8 +1,14 @@
-mod tui;
-mod data;
+use clap::Parser;
+use resume_utd::cli;
+use std::process::ExitCode;
 
-use std::io;
-
-fn main() -> io::Result<()> {
-    tui::run_app()
-}
\ No newline at end of file
+fn main() -> ExitCode {
+    let cli = cli::Cli::parse();
+    match cli::run(cli) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
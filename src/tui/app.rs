@@ -1,14 +1,19 @@
-use crate::tui::components::{Sidebar, ContentPane};
-use crate::data::DataManager;
-use ratatui::style::{Color, Style};
-use ratatui::text::{Line, Span};
+use crate::tui::components::{Sidebar, ContentPane, ConfirmDialog, PromptDialog, RecoveryScreen};
+use crate::tui::components::confirm::ConfirmAction;
+use crate::tui::components::prompt::PromptAction;
+use crate::tui::components::recovery::RecoveryAction;
+use crate::tui::components::content_pane::Status;
+use crate::data::{DataError, DataManager};
+use crate::data::manager::Resume;
+use crate::export::batch::{BatchExporter, Outcome};
+use crate::export::{Destination, ExportResult, Exporter};
+use crossterm::event::KeyEvent;
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     Frame,
 };
 use std::fs;
 use std::path::Path;
-use handlebars::{Context, Handlebars, Helper, HelperResult, Output, RenderContext};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum MenuItem {
@@ -17,7 +22,9 @@ pub enum MenuItem {
     Experience,
     Projects,
     Skills,
+    Check,
     Export,
+    Backups,
     Exit,
 }
 
@@ -35,7 +42,9 @@ impl MenuItem {
             MenuItem::Experience => "Experience",
             MenuItem::Projects => "Projects",
             MenuItem::Skills => "Skills",
+            MenuItem::Check => "Check Resume",
             MenuItem::Export => "Export",
+            MenuItem::Backups => "Restore Backup",
             MenuItem::Exit => "Exit Application",
         }
     }
@@ -47,7 +56,9 @@ impl MenuItem {
             MenuItem::Experience,
             MenuItem::Projects,
             MenuItem::Skills,
+            MenuItem::Check,
             MenuItem::Export,
+            MenuItem::Backups,
             MenuItem::Exit,
         ]
     }
@@ -59,8 +70,10 @@ impl MenuItem {
             3 => Some(MenuItem::Experience),
             4 => Some(MenuItem::Projects),
             5 => Some(MenuItem::Skills),
-            6 => Some(MenuItem::Export),
-            7 => Some(MenuItem::Exit),
+            6 => Some(MenuItem::Check),
+            7 => Some(MenuItem::Export),
+            8 => Some(MenuItem::Backups),
+            9 => Some(MenuItem::Exit),
             _ => None,
         }
     }
@@ -73,39 +86,75 @@ pub struct AppState {
     pub sidebar: Sidebar,
     pub content_pane: ContentPane,
     pub data_manager: DataManager,
+    /// Set when the data file could not be loaded; replaces the editor.
+    pub recovery: Option<RecoveryScreen>,
+    /// A question waiting for yes or no; it takes the keyboard until answered.
+    pub confirm: Option<ConfirmDialog>,
+    /// A text question waiting for an answer, such as the save-as path.
+    pub prompt: Option<PromptDialog>,
+    /// The resume as last loaded from or saved to disk; `None` if what is
+    /// being edited has never been written, as after a recovery.
+    saved: Option<Resume>,
+    /// Whether the undo history is kept between sessions.
+    keep_history: bool,
     pub should_exit: bool,
 }
 
 impl AppState {
     pub fn new(file_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let data_manager = match DataManager::new(file_path) {
-            Ok(r) => r,
-            Err(e) => {
-                let template = "data/data_template.json";
-                let target = "data/resume.json";
-
-                if Path::new(template).exists() {
-                    fs::copy(template, target)?;
-                    eprintln!("Created new {} from template", target);
-
-                    DataManager::new(file_path)?
-                } else {
-                    eprintln!("Template {} not found, cannot recover", template);
-                    std::process::exit(1);
+        let (data_manager, recovery) = match DataManager::new(file_path) {
+            Ok(data_manager) => (data_manager, None),
+            Err(e) if e.is_not_found() => {
+                let target = Path::new(file_path);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
                 }
+                // Go through DataManager so the copy is written in the
+                // format `file_path` asks for.
+                let mut data_manager = DataManager::from_template(file_path)?;
+                data_manager.save()?;
+                eprintln!("Created new {} from template", target.display());
+
+                (data_manager, None)
+            }
+            Err(e) => {
+                let data_manager = DataManager::empty(file_path);
+                let latest_backup = data_manager.backups().into_iter().next();
+                (data_manager, Some(RecoveryScreen::new(e, latest_backup)))
             }
         };
+        let mut content_pane = ContentPane::new();
+        if let Some(version) = data_manager.migrated_from {
+            content_pane.set_status(Status::Success(format!(
+                "Upgraded {} from schema version {}; it is rewritten on the next save, which keeps the original as .v{}.bak",
+                data_manager.file_path, version, version
+            )));
+        }
+
+        // An upgraded file only reaches disk on save, so it starts out unsaved.
+        let saved = (recovery.is_none() && data_manager.migrated_from.is_none()).then(|| data_manager.resume.clone());
+
         Ok(Self {
             current_menu: MenuItem::PersonalInfo,
             focus: Focus::Sidebar,
             sidebar: Sidebar::new(),
-            content_pane: ContentPane::new(),
+            content_pane,
             data_manager,
+            recovery,
+            confirm: None,
+            prompt: None,
+            saved,
+            keep_history: false,
             should_exit: false,
         })
     }
 
     pub fn render(&self, frame: &mut Frame) {
+        if let Some(recovery) = &self.recovery {
+            recovery.render(frame, frame.area());
+            return;
+        }
+
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
@@ -114,8 +163,70 @@ impl AppState {
             ])
             .split(frame.area());
 
-        self.sidebar.render(frame, chunks[0], &self.current_menu, &self.focus);
+        self.sidebar.render(frame, chunks[0], &self.current_menu, &self.focus, self.is_dirty());
         self.content_pane.render(frame, chunks[1], &self.current_menu, &self.focus, &self.data_manager);
+        if let Some(confirm) = &self.confirm {
+            confirm.render(frame, frame.area());
+        }
+        if let Some(prompt) = &self.prompt {
+            prompt.render(frame, frame.area());
+        }
+    }
+
+    /// Keeps the undo history in a file next to the data from now on,
+    /// continuing the one from the last session if it still applies.
+    pub fn keep_history(&mut self) {
+        self.keep_history = true;
+        if self.recovery.is_none() && self.data_manager.persist_history() {
+            self.content_pane.set_status(Status::Success(
+                "Picked up the undo history from the last session (Ctrl+Z to undo)".to_string(),
+            ));
+        }
+    }
+
+    /// Carries out the option chosen on the recovery screen.
+    pub fn recover(&mut self) {
+        let Some(recovery) = &mut self.recovery else {
+            return;
+        };
+        let file_path = self.data_manager.file_path.clone();
+
+        let result = match recovery.selected_action() {
+            RecoveryAction::OpenAnyway => DataManager::open_lenient(&file_path).map(|(data_manager, dropped)| {
+                let status = if dropped.is_empty() {
+                    Status::Success(format!("Opened {}", file_path))
+                } else {
+                    Status::Error(format!("Opened {} without: {}. Saving will overwrite them.", file_path, dropped.join(", ")))
+                };
+                (data_manager, status)
+            }),
+            RecoveryAction::RestoreBackup(backup) => DataManager::from_backup(&file_path, &backup).map(|data_manager| {
+                let status = Status::Success(format!("Restored backup from {}; save to write it back", backup.label()));
+                (data_manager, status)
+            }),
+            RecoveryAction::StartFromTemplate => DataManager::from_template(&file_path).map(|data_manager| {
+                let status = Status::Success(format!("Started from template; {} is unchanged until you save", file_path));
+                (data_manager, status)
+            }),
+            RecoveryAction::Quit => {
+                self.should_exit = true;
+                return;
+            }
+        };
+
+        match result {
+            Ok((data_manager, status)) => {
+                // Whatever was recovered differs from the broken file on disk.
+                self.saved = None;
+                self.data_manager = data_manager;
+                if self.keep_history {
+                    self.data_manager.persist_history();
+                }
+                self.recovery = None;
+                self.content_pane.set_status(status);
+            }
+            Err(e) => recovery.error = e,
+        }
     }
 
     pub fn next_menu(&mut self) {
@@ -125,17 +236,10 @@ impl AppState {
                 let next_index = (current_index + 1) % items.len();
                 self.current_menu = items[next_index].clone();
             }
-        } else {
-            if self.content_pane.is_editing {
-                self.content_pane.next_field();
-            } else {
-                match &self.current_menu {
-                    MenuItem::Education | MenuItem::Experience | MenuItem::Projects => {
-                        self.content_pane.next_entry(&self.data_manager, &self.current_menu);
-                    }
-                    _ => {}
-                }
-            }
+        } else if self.content_pane.is_editing {
+            self.content_pane.next_field();
+        } else if is_list_menu(&self.current_menu) {
+            self.content_pane.next_entry(&self.data_manager, &self.current_menu);
         }
     }
 
@@ -150,26 +254,17 @@ impl AppState {
                 };
                 self.current_menu = items[prev_index].clone();
             }
-        } else {
-            if self.content_pane.is_editing {
-                self.content_pane.previous_field();
-            } else {
-                match &self.current_menu {
-                    MenuItem::Education | MenuItem::Experience | MenuItem::Projects => {
-                        self.content_pane.previous_entry(&self.data_manager, &self.current_menu);
-                    }
-                    _ => {}
-                }
-            }
+        } else if self.content_pane.is_editing {
+            self.content_pane.previous_field();
+        } else if is_list_menu(&self.current_menu) {
+            self.content_pane.previous_entry(&self.data_manager, &self.current_menu);
         }
     }
 
     pub fn select_current_menu(&mut self) {
         if self.focus == Focus::Sidebar {
             match &self.current_menu {
-                MenuItem::Exit => {
-                    self.exit();
-                }
+                MenuItem::Exit => self.request_exit(),
                 MenuItem::Export => {
                     // self.focus = Focus::Content;
                     self.content_pane.trigger_export();
@@ -180,54 +275,241 @@ impl AppState {
                     self.content_pane.select_first_entry(&self.data_manager, &self.current_menu);
                 }
             }
+        } else if self.content_pane.is_editing {
+            if self.content_pane.handle_enter() {
+                self.commit_edit();
+            }
         } else {
             match &self.current_menu {
                 MenuItem::Export => {
                     match self.export_resume() {
-                        Ok(message) => {
-                            self.content_pane.set_export_status(message);
+                        Ok(result) => {
+                            let mut message = match &result.pdf {
+                                Some(pdf) => format!("Generated {} and {}", result.destination, pdf.pdf.display()),
+                                None => format!("Generated {}", result.destination),
+                            };
+                            for warning in &result.warnings {
+                                message.push_str(&format!("\n  {}", warning));
+                            }
+                            self.content_pane.set_export_status(Status::Success(message));
                         }
                         Err(e) => {
-                            self.content_pane.set_export_status(format!("Export failed: {}", e));
+                            self.content_pane.set_export_status(Status::Error(format!("Export failed: {}", e)));
                         }
                     }
                 }
-                MenuItem::Education | MenuItem::Experience | MenuItem::Projects => {
+                MenuItem::Education | MenuItem::Experience | MenuItem::Projects | MenuItem::Skills => {
                     if let Some(selected_idx) = self.content_pane.selected_entry {
-                        let max_entries = match &self.current_menu {
-                            MenuItem::Education => self.data_manager.resume.education.len(),
-                            MenuItem::Experience => self.data_manager.resume.experience.len(),
-                            MenuItem::Projects => self.data_manager.resume.projects.len(),
-                            _ => 0,
-                        };
-                        
-                        if selected_idx < max_entries {
+                        if selected_idx < entry_count(&self.data_manager, &self.current_menu) {
                             self.content_pane.enter_entry_edit_mode(&self.current_menu, selected_idx, &self.data_manager);
                         } else {
                             self.content_pane.parent_menu = Some(self.current_menu.clone());
-                            self.content_pane.enter_edit_mode(&self.current_menu);
+                            self.content_pane.enter_edit_mode(&self.current_menu, &self.data_manager);
                         }
                     } else {
                         self.content_pane.parent_menu = Some(self.current_menu.clone());
-                        self.content_pane.enter_edit_mode(&self.current_menu);
+                        self.content_pane.enter_edit_mode(&self.current_menu, &self.data_manager);
                     }
                 }
+                MenuItem::Check => self.jump_to_issue(),
+                MenuItem::Backups => self.restore_backup(),
+                MenuItem::Exit => {}
                 _ => {
-                    let needs_save = self.content_pane.handle_enter();
-                    if needs_save {
-                        if let Err(e) = self.content_pane.save_edited_data(&mut self.data_manager) {
-                            eprintln!("Error saving data: {}", e);
-                        } else {
-                            if let Err(e) = self.data_manager.save() {
-                                eprintln!("Error saving to file: {}", e);
-                            }
-                        }
+                    self.content_pane.parent_menu = None;
+                    self.content_pane.enter_edit_mode(&self.current_menu, &self.data_manager);
+                }
+            }
+        }
+    }
+
+    /// Applies the open form to the data, without writing it to disk.
+    /// Returns false if the form stays open because a value was rejected.
+    fn commit_edit(&mut self) -> bool {
+        let result = self.content_pane.save_edited_data(&mut self.data_manager);
+
+        // Keep the form open so a missing required field can be filled in.
+        if let Err(e @ DataError::Validation(_)) = &result {
+            self.content_pane.set_status(Status::Error(e.to_string()));
+            return false;
+        }
+
+        let section = self.content_pane.parent_menu.as_ref().unwrap_or(&self.current_menu).title();
+        self.content_pane.finish_edit();
+        match result {
+            Ok(()) => self.content_pane.set_status(Status::Success(format!("Updated {}; Ctrl+S to save", section))),
+            Err(e) => self.content_pane.set_status(Status::Error(format!("Could not update {}: {}", section, e))),
+        }
+        true
+    }
+
+    /// Closes the edit form, asking first if anything was typed into it.
+    pub fn cancel_edit(&mut self) {
+        if self.content_pane.form_changed() {
+            self.confirm = Some(
+                ConfirmDialog::new("Discard the changes made in this form?", ConfirmAction::DiscardEdit)
+                    .with_yes("discard them"),
+            );
+        } else {
+            self.discard_edit();
+        }
+    }
+
+    fn discard_edit(&mut self) {
+        self.content_pane.finish_edit();
+        if let Some(parent) = &self.content_pane.parent_menu {
+            self.current_menu = parent.clone();
+        }
+    }
+
+    /// Whether there is anything that has not been written to disk,
+    /// including a form that has been typed into but not applied.
+    pub fn is_dirty(&self) -> bool {
+        self.saved.as_ref() != Some(&self.data_manager.resume) || self.content_pane.form_changed()
+    }
+
+    /// Writes the data to disk, applying the open form first. Returns
+    /// whether everything was saved.
+    pub fn save(&mut self) -> bool {
+        if self.content_pane.is_editing && !self.commit_edit() {
+            return false;
+        }
+        match self.data_manager.save() {
+            Ok(()) => {
+                self.saved = Some(self.data_manager.resume.clone());
+                self.content_pane
+                    .set_status(Status::Success(format!("Saved to {}", self.data_manager.file_path)));
+                true
+            }
+            Err(e) => {
+                self.content_pane.set_status(Status::Error(format!("Error saving to file: {}", e)));
+                false
+            }
+        }
+    }
+
+    /// Asks for a new path to save the data under.
+    pub fn request_save_as(&mut self) {
+        self.prompt = Some(PromptDialog::new(
+            "Save as (the extension picks json, yaml or toml)",
+            self.data_manager.file_path.clone(),
+            PromptAction::SaveAs,
+        ));
+    }
+
+    /// Carries out the open prompt with the text entered.
+    pub fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt.take() else {
+            return;
+        };
+        match prompt.action {
+            PromptAction::SaveAs => {
+                let path = prompt.value().trim();
+                if path.is_empty() {
+                    return;
+                }
+                if self.content_pane.is_editing && !self.commit_edit() {
+                    return;
+                }
+                match self.data_manager.save_as(path) {
+                    Ok(()) => {
+                        self.saved = Some(self.data_manager.resume.clone());
+                        self.content_pane.set_status(Status::Success(format!("Saved to {}", path)));
                     }
+                    Err(e) => self.content_pane.set_status(Status::Error(format!("Error saving to {}: {}", path, e))),
                 }
             }
         }
     }
 
+    pub fn cancel_prompt(&mut self) {
+        self.prompt = None;
+    }
+
+    /// Reloads the data file, asking first if that would lose changes.
+    pub fn request_revert(&mut self) {
+        if self.is_dirty() {
+            self.confirm = Some(
+                ConfirmDialog::new(
+                    format!("Drop all unsaved changes and reload {}?", self.data_manager.file_path),
+                    ConfirmAction::Revert,
+                )
+                .with_yes("reload"),
+            );
+        } else {
+            self.revert();
+        }
+    }
+
+    /// Replaces the data with what is on disk. This can itself be undone.
+    fn revert(&mut self) {
+        match self.data_manager.revert() {
+            Ok(()) => {
+                self.content_pane.finish_edit();
+                self.saved = Some(self.data_manager.resume.clone());
+                self.content_pane.set_status(Status::Success(format!(
+                    "Reloaded {}; Ctrl+Z to get the changes back",
+                    self.data_manager.file_path
+                )));
+            }
+            Err(e) => self.content_pane.set_status(Status::Error(format!("Could not reload: {}", e))),
+        }
+    }
+
+    /// Quits, first asking whether to save if there are unsaved changes.
+    pub fn request_exit(&mut self) {
+        if self.is_dirty() {
+            self.confirm = Some(
+                ConfirmDialog::new(
+                    format!("Save changes to {} before quitting?", self.data_manager.file_path),
+                    ConfirmAction::SaveAndExit,
+                )
+                .with_yes("save and quit")
+                .with_no("quit without saving", ConfirmAction::Exit),
+            );
+        } else {
+            self.exit();
+        }
+    }
+
+    /// Moves to the section and entry the selected validation issue is in.
+    fn jump_to_issue(&mut self) {
+        let issues = self.data_manager.validate();
+        let Some(issue) = self.content_pane.selected_entry.and_then(|i| issues.get(i)) else {
+            return;
+        };
+
+        let (section, entry) = issue.location();
+        self.current_menu = match section {
+            "education" => MenuItem::Education,
+            "experience" => MenuItem::Experience,
+            "projects" => MenuItem::Projects,
+            "skills" => MenuItem::Skills,
+            "profiles" => MenuItem::Export,
+            _ => MenuItem::PersonalInfo,
+        };
+        self.content_pane.selected_entry = entry;
+    }
+
+    /// Replaces the data with the selected backup. Nothing is written until
+    /// it is saved, and the restore can be undone.
+    fn restore_backup(&mut self) {
+        let backups = self.data_manager.backups();
+        let Some(backup) = self.content_pane.selected_entry.and_then(|i| backups.get(i)) else {
+            return;
+        };
+
+        match self.data_manager.restore_backup(backup) {
+            Ok(()) => {
+                self.content_pane.select_first_entry(&self.data_manager, &MenuItem::Backups);
+                self.content_pane.set_status(Status::Success(format!(
+                    "Restored backup from {}; Ctrl+S to save it, which backs up the current file first",
+                    backup.label()
+                )));
+            }
+            Err(e) => self.content_pane.set_status(Status::Error(format!("Restore failed: {}", e))),
+        }
+    }
+
     pub fn switch_focus(&mut self) {
         self.focus = match self.focus {
             Focus::Sidebar => Focus::Content,
@@ -235,23 +517,183 @@ impl AppState {
         };
     }
 
-    pub fn handle_text_input(&mut self, c: char) {
-        if self.focus == Focus::Content {
-            self.content_pane.handle_text_input(c);
+    /// Offers a key to the form field being edited. Returns whether it was used.
+    pub fn handle_field_key(&mut self, key: KeyEvent) -> bool {
+        self.focus == Focus::Content && self.content_pane.handle_field_key(key)
+    }
+
+    /// Keys that act on the Export screen while it has focus.
+    pub fn handle_export_key(&mut self, c: char) {
+        if self.focus != Focus::Content || self.current_menu != MenuItem::Export {
+            return;
+        }
+        match c {
+            'f' => self.content_pane.next_export_format(),
+            'v' => self.content_pane.next_export_profile(&self.data_manager.resume.profiles),
+            'a' => self.export_all(),
+            'p' => self.content_pane.toggle_pdf(),
+            'e' => self.content_pane.next_pdf_engine(),
+            _ => {}
+        }
+    }
+
+    /// Keys that act on the selected entry of a list section.
+    pub fn handle_entry_key(&mut self, c: char) {
+        match c {
+            'd' => self.request_delete_entry(),
+            'c' => self.duplicate_entry(),
+            'K' => self.move_entry(false),
+            'J' => self.move_entry(true),
+            _ => {}
         }
     }
 
-    pub fn handle_backspace(&mut self) {
-        if self.focus == Focus::Content {
-            self.content_pane.handle_backspace();
+    /// Index of the entry selected in a list section, if an entry (rather
+    /// than the "+ Add new" row) is selected and the list has focus.
+    fn selected_list_entry(&self) -> Option<usize> {
+        if self.focus != Focus::Content || self.content_pane.is_editing {
+            return None;
         }
+        let index = self.content_pane.selected_entry?;
+        (index < entry_count(&self.data_manager, &self.current_menu)).then_some(index)
     }
 
-    pub fn handle_number_input(&mut self, num: u8) {
-        if self.focus == Focus::Sidebar {
-            if let Some(menu_item) = MenuItem::from_number(num) {
-                self.current_menu = menu_item;
+    /// Asks before deleting the selected entry.
+    pub fn request_delete_entry(&mut self) {
+        let Some(index) = self.selected_list_entry() else {
+            return;
+        };
+        let name = entry_name(&self.data_manager, &self.current_menu, index).unwrap_or_default();
+        self.confirm = Some(ConfirmDialog::new(
+            format!("Delete '{}' from {}?", name, self.current_menu.title()),
+            ConfirmAction::DeleteEntry { menu: self.current_menu.clone(), index },
+        ));
+    }
+
+    /// Carries out the action of the open confirmation dialog.
+    pub fn confirm(&mut self) {
+        if let Some(dialog) = self.confirm.take() {
+            self.run_confirm_action(dialog.action);
+        }
+    }
+
+    /// Answers the open confirmation dialog with "no", which cancels it
+    /// unless the dialog gives "no" an action of its own.
+    pub fn decline(&mut self) {
+        if let Some(action) = self.confirm.take().and_then(|dialog| dialog.no_action()) {
+            self.run_confirm_action(action);
+        }
+    }
+
+    fn run_confirm_action(&mut self, action: ConfirmAction) {
+        match action {
+            ConfirmAction::DeleteEntry { menu, index } => {
+                let name = entry_name(&self.data_manager, &menu, index).unwrap_or_default();
+                let result = match menu {
+                    MenuItem::Education => self.data_manager.delete_education(index),
+                    MenuItem::Experience => self.data_manager.delete_experience(index),
+                    MenuItem::Projects => self.data_manager.delete_project(index),
+                    MenuItem::Skills => self.data_manager.delete_skill_category(index),
+                    _ => return,
+                };
+                // The row below moves up into the selection, or the
+                // "+ Add new" row does if the last entry went.
+                self.report_entry_change(result, format!("Deleted '{}'", name));
             }
+            ConfirmAction::DiscardEdit => self.discard_edit(),
+            ConfirmAction::Revert => self.revert(),
+            ConfirmAction::SaveAndExit => {
+                if self.save() {
+                    self.exit();
+                }
+            }
+            ConfirmAction::Exit => self.exit(),
+        }
+    }
+
+    pub fn cancel_confirm(&mut self) {
+        self.confirm = None;
+    }
+
+    /// Moves the selected entry one place down, or up, keeping it selected.
+    fn move_entry(&mut self, down: bool) {
+        let Some(index) = self.selected_list_entry() else {
+            return;
+        };
+        let target = if down { index + 1 } else { index.wrapping_sub(1) };
+        if target >= entry_count(&self.data_manager, &self.current_menu) {
+            return;
+        }
+        let result = match self.current_menu {
+            MenuItem::Education => self.data_manager.move_education(index, target),
+            MenuItem::Experience => self.data_manager.move_experience(index, target),
+            MenuItem::Projects => self.data_manager.move_project(index, target),
+            MenuItem::Skills => self.data_manager.move_skill_category(index, target),
+            _ => return,
+        };
+        if result.is_ok() {
+            self.content_pane.selected_entry = Some(target);
+        }
+        let name = entry_name(&self.data_manager, &self.current_menu, target).unwrap_or_default();
+        self.report_entry_change(result, format!("Moved '{}' to position {}", name, target + 1));
+    }
+
+    /// Inserts a copy of the selected entry below it and selects the copy.
+    fn duplicate_entry(&mut self) {
+        let Some(index) = self.selected_list_entry() else {
+            return;
+        };
+        let result = match self.current_menu {
+            MenuItem::Education => self.data_manager.duplicate_education(index),
+            MenuItem::Experience => self.data_manager.duplicate_experience(index),
+            MenuItem::Projects => self.data_manager.duplicate_project(index),
+            MenuItem::Skills => self.data_manager.duplicate_skill_category(index),
+            _ => return,
+        };
+        let name = entry_name(&self.data_manager, &self.current_menu, index).unwrap_or_default();
+        let result = result.map(|copy| self.content_pane.selected_entry = Some(copy));
+        self.report_entry_change(result, format!("Duplicated '{}'", name));
+    }
+
+    fn report_entry_change(&mut self, result: Result<(), DataError>, message: String) {
+        match result {
+            Ok(()) => self.content_pane.set_status(Status::Success(format!("{}; Ctrl+S to save", message))),
+            Err(e) => self.content_pane.set_status(Status::Error(e.to_string())),
+        }
+    }
+
+    /// Reverts the latest edit. Nothing is written until it is saved.
+    pub fn undo(&mut self) {
+        if self.content_pane.is_editing {
+            return;
+        }
+        let result = self.data_manager.undo();
+        self.report_history_step("undo", "Undid", result);
+    }
+
+    /// Re-applies the latest undone edit.
+    pub fn redo(&mut self) {
+        if self.content_pane.is_editing {
+            return;
+        }
+        let result = self.data_manager.redo();
+        self.report_history_step("redo", "Redid", result);
+    }
+
+    fn report_history_step(&mut self, action: &str, done: &str, result: Result<Option<String>, DataError>) {
+        let status = match result {
+            Ok(None) => Status::Error(format!("Nothing to {}", action)),
+            Ok(Some(label)) => Status::Success(format!("{} {}; Ctrl+S to save", done, label)),
+            Err(e) => Status::Error(format!("Could not {}: {}", action, e)),
+        };
+        self.content_pane.set_status(status);
+    }
+
+    pub fn handle_number_input(&mut self, num: u8) {
+        if self.focus == Focus::Sidebar
+            && let Some(menu_item) = MenuItem::from_number(num)
+        {
+            self.current_menu = menu_item;
         }
     }
 
@@ -259,46 +701,68 @@ impl AppState {
         self.should_exit = true;
     }
 
-    pub fn export_resume(&mut self) -> Result<String, Box<dyn std::error::Error>> {
-        let mut handlebars = Handlebars::new();
-        handlebars.register_template_file("resume", "templates/resume.tex.hbs")?;
-        handlebars.register_helper("bold", Box::new(bold_helper));
-        handlebars.register_helper("href", Box::new(href_helper));
-
-        let tex_output = handlebars.render("resume", &self.data_manager.resume)?;
+    /// Batch-exports every profile in every format; see `export::batch`.
+    fn export_all(&mut self) {
+        let report = BatchExporter::new("output")
+            .with_pdf(self.content_pane.pdf_engine)
+            .run(&self.data_manager.resume);
+        let mut message = format!("Exported all to output/: {}", report.summary());
+        for target in report.targets.iter().filter(|t| matches!(t.outcome, Outcome::Failed(_))) {
+            message.push_str(&format!("\n  {}", target));
+        }
+        if let Some(e) = &report.manifest_error {
+            message.push_str(&format!("\n  could not record export hashes: {}", e));
+        }
+        let status = if report.failed() > 0 { Status::Error(message) } else { Status::Success(message) };
+        self.content_pane.set_export_status(status);
+    }
 
-        let out_path = Path::new("output/resume.tex");
-        fs::create_dir_all("output")?;
-        fs::write(out_path, tex_output)?;
+    /// Exports with the chosen format and profile. A profile that no longer
+    /// exists is an error rather than a silent export of everything.
+    pub fn export_resume(&mut self) -> Result<ExportResult, Box<dyn std::error::Error>> {
+        let format = self.content_pane.export_format;
+        let profile_name = self.content_pane.export_profile.as_deref();
+        let profile = profile_name.map(|name| self.data_manager.profile(name).cloned()).transpose()?;
+        let result = Exporter::new(format)
+            .with_destination(Destination::File(format.default_output_for(profile_name)))
+            .with_profile(profile)
+            .with_pdf(self.content_pane.pdf_engine)
+            .export(&self.data_manager.resume)?;
+        Ok(result)
+    }
+}
 
-        Ok(format!("Generated {}", out_path.display()))
+/// Number of entries in a list section, not counting the "+ Add new" row.
+fn entry_count(data_manager: &DataManager, menu: &MenuItem) -> usize {
+    let resume = &data_manager.resume;
+    match menu {
+        MenuItem::Education => resume.education.len(),
+        MenuItem::Experience => resume.experience.len(),
+        MenuItem::Projects => resume.projects.len(),
+        MenuItem::Skills => resume.skills.len(),
+        _ => 0,
     }
 }
 
-fn bold_helper(
-    h: &Helper<'_>,
-    _: &Handlebars<'_>,
-    _: &Context,
-    _: &mut RenderContext<'_, '_>,
-    out: &mut dyn Output,
-) -> HelperResult {
-    if let Some(param) = h.param(0) {
-        let text = param.value().as_str().unwrap_or("");
-        out.write(&format!("\\textbf{{{}}}", text))?; 
-    }
-    Ok(())
+fn entry_name(data_manager: &DataManager, menu: &MenuItem, index: usize) -> Option<String> {
+    let resume = &data_manager.resume;
+    match menu {
+        MenuItem::Education => resume.education.get(index).map(|e| e.name.clone()),
+        MenuItem::Experience => resume.experience.get(index).map(|e| e.company.clone()),
+        MenuItem::Projects => resume.projects.get(index).map(|p| p.name.clone()),
+        MenuItem::Skills => resume.skills.get(index).map(|c| c.name.clone()),
+        _ => None,
+    }
 }
 
-fn href_helper(
-    h: &Helper<'_>,
-    _: &Handlebars<'_>,
-    _: &Context,
-    _: &mut RenderContext<'_, '_>,
-    out: &mut dyn Output,
-) -> HelperResult {
-    if let Some(param) = h.param(0) {
-        let text = param.value().as_str().unwrap_or("");
-        out.write(&format!("\\href{{{}}}{{GitHub Repo Link}}", text))?;
-    }
-    Ok(())
+fn is_list_menu(menu: &MenuItem) -> bool {
+    matches!(
+        menu,
+        MenuItem::Education
+            | MenuItem::Experience
+            | MenuItem::Projects
+            | MenuItem::Skills
+            | MenuItem::Check
+            | MenuItem::Backups
+    )
 }
@@ -0,0 +1,103 @@
+use crate::tui::app::MenuItem;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// What happens if the dialog is answered.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfirmAction {
+    DeleteEntry { menu: MenuItem, index: usize },
+    /// Close the edit form without keeping what was typed.
+    DiscardEdit,
+    /// Reload the data file, dropping unsaved changes.
+    Revert,
+    SaveAndExit,
+    Exit,
+}
+
+/// A yes/no question drawn over the rest of the screen. It holds the action
+/// until it is answered, and nothing happens if it is cancelled.
+#[derive(Debug)]
+pub struct ConfirmDialog {
+    pub message: String,
+    pub action: ConfirmAction,
+    /// What "yes" does, for the key hint.
+    yes: String,
+    /// What "no" does, if it is more than cancelling.
+    no: Option<(String, ConfirmAction)>,
+}
+
+impl ConfirmDialog {
+    pub fn new(message: impl Into<String>, action: ConfirmAction) -> Self {
+        Self {
+            message: message.into(),
+            action,
+            yes: "confirm".to_string(),
+            no: None,
+        }
+    }
+
+    pub fn with_yes(mut self, label: impl Into<String>) -> Self {
+        self.yes = label.into();
+        self
+    }
+
+    /// Makes "no" do `action` rather than cancel; Esc still cancels.
+    pub fn with_no(mut self, label: impl Into<String>, action: ConfirmAction) -> Self {
+        self.no = Some((label.into(), action));
+        self
+    }
+
+    /// The action for "no", if it does more than cancel.
+    pub fn no_action(&self) -> Option<ConfirmAction> {
+        self.no.as_ref().map(|(_, action)| action.clone())
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let area = centered(area, 60, 7);
+        let keys = match &self.no {
+            Some((no, _)) => format!("y / Enter to {}, n to {}, Esc to cancel", self.yes, no),
+            None => format!("y / Enter to {}, n / Esc to cancel", self.yes),
+        };
+        let lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(self.message.as_str(), Style::default().add_modifier(Modifier::BOLD))),
+            Line::from(""),
+            Line::from(Span::styled(keys, Style::default().fg(Color::Cyan))),
+        ];
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title("Confirm")
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Yellow)),
+            )
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(paragraph, area);
+    }
+}
+
+/// A `width` percent wide, `height` rows tall box in the middle of `area`.
+pub(crate) fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [_, row, _] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(height), Constraint::Fill(1)])
+        .areas(area);
+    let [_, column, _] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - width) / 2),
+            Constraint::Percentage(width),
+            Constraint::Percentage((100 - width) / 2),
+        ])
+        .areas(row);
+    column
+}
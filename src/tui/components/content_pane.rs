@@ -1,5 +1,14 @@
 use crate::tui::app::{MenuItem, Focus};
-use crate::data::DataManager;
+use crate::data::{DataError, DataManager};
+use crate::data::date::DateRange;
+use crate::data::validate::Severity;
+use crate::export::pdf::Engine;
+use crate::export::{Format, TemplateSource};
+use crate::data::manager::{Contact, Point, Profile, Skill, SkillCategory};
+use crate::data::profile::ResumeProfile;
+use crate::tui::components::row_editor::{Row, RowEditor};
+use crate::tui::components::text_input::TextInput;
+use crossterm::event::KeyEvent;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
@@ -7,19 +16,44 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
 #[derive(Debug)]
 pub struct ContentPane {
-    content: String,
     current_field: usize,
-    fields: Vec<String>,
+    fields: Vec<TextInput>,
+    /// Field values and rows as the form was opened, to tell whether
+    /// anything was typed.
+    original_fields: Vec<String>,
+    original_points: Vec<Point>,
+    original_skills: Vec<Skill>,
+    original_profiles: Vec<Profile>,
     pub is_editing: bool,
-    cursor_position: usize,
     pub selected_entry: Option<usize>,
     pub entry_type: Option<EntryType>,
     pub parent_menu: Option<MenuItem>,
     current_menu: Option<MenuItem>,
-    pub export_status: Option<String>,
+    pub export_status: Option<Status>,
+    pub export_format: Format,
+    /// Profile to export; `None` exports everything.
+    pub export_profile: Option<String>,
+    pub pdf_engine: Option<Engine>,
+    /// Whether `pdf_engine` was on `PATH` when it was picked, so the export
+    /// screen does not search `PATH` on every frame.
+    pdf_engine_found: bool,
+    status: Option<Status>,
+    /// Bullet points of the entry being edited, for forms that have them.
+    pub points: Option<RowEditor<Point>>,
+    /// Skills of the category being edited.
+    pub skills: Option<RowEditor<Skill>>,
+    /// Profile links of the personal info being edited.
+    pub profiles: Option<RowEditor<Profile>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Status {
+    Success(String),
+    Error(String),
 }
 
 #[derive(Debug, Clone)]
@@ -27,41 +61,70 @@ pub enum EntryType {
     Education(usize),
     Experience(usize),
     Project(usize),
+    Skill(usize),
+}
+
+impl Default for ContentPane {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ContentPane {
     pub fn new() -> Self {
         Self {
-            content: "Welcome to Resume Builder! Use arrow keys or j/k to navigate, Enter to select.".to_string(),
             current_field: 0,
             fields: Vec::new(),
+            original_fields: Vec::new(),
+            original_points: Vec::new(),
+            original_skills: Vec::new(),
+            original_profiles: Vec::new(),
             is_editing: false,
-            cursor_position: 0,
             selected_entry: None,
             entry_type: None,
             parent_menu: None,
             current_menu: None,
             export_status: None,
+            export_format: Format::Latex,
+            export_profile: None,
+            pdf_engine: None,
+            pdf_engine_found: false,
+            status: None,
+            points: None,
+            skills: None,
+            profiles: None,
         }
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect, current_menu: &MenuItem, focus: &Focus, data_manager: &DataManager) {
-        let title = format!("{}", current_menu.title());
+        let title = current_menu.title();
         
-        let content = if self.is_editing {
-            self.render_editing_form(current_menu)
+        let mut content = if self.is_editing {
+            // Two columns go to the borders.
+            self.render_editing_form(current_menu, usize::from(area.width.saturating_sub(2)))
         } else {
             match current_menu {
                 MenuItem::PersonalInfo => self.render_personal_info(data_manager, focus),
                 MenuItem::Education => self.render_education(data_manager, focus),
                 MenuItem::Experience => self.render_experience(data_manager, focus),
                 MenuItem::Projects => self.render_projects(data_manager, focus),
-                MenuItem::Skills => self.render_skills(focus),
-                MenuItem::Export => self.render_export(focus),
+                MenuItem::Skills => self.render_skills(data_manager, focus),
+                MenuItem::Check => self.render_check(data_manager, focus),
+                MenuItem::Export => self.render_export(data_manager, focus),
+                MenuItem::Backups => self.render_backups(data_manager, focus),
                 MenuItem::Exit => self.render_exit(),
             }
         };
 
+        if let Some(status) = &self.status {
+            let line = match status {
+                Status::Success(message) => Span::styled(message.as_str(), Style::default().fg(Color::Green)),
+                Status::Error(message) => Span::styled(message.as_str(), Style::default().fg(Color::Red)),
+            };
+            content.push(Line::from(""));
+            content.push(Line::from(line));
+        }
+
         let block = Block::default()
             .title(title)
             .borders(Borders::ALL)
@@ -79,28 +142,22 @@ impl ContentPane {
         frame.render_widget(paragraph, area);
     }
 
-    fn get_menu_description(&self, menu: &MenuItem) -> &'static str {
-        match menu {
-            MenuItem::PersonalInfo => "Enter your personal information",
-            MenuItem::Education => "Add your educational background",
-            MenuItem::Experience => "List your work experience",
-            MenuItem::Projects => "Showcase your projects",
-            MenuItem::Skills => "Highlight your skills",
-            MenuItem::Export => "Export your resume",
-            MenuItem::Exit => "Exit the application",
-        }
-    }
-
-    fn render_personal_info(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line> {
+    fn render_personal_info(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line<'_>> {
         let mut lines = vec![
             Line::from(""),
         ];
 
         lines.push(Line::from(format!("Name: {}", data_manager.resume.name)));
-        lines.push(Line::from(format!("Contact: {}", data_manager.resume.contact)));
+        let contact = &data_manager.resume.contact;
+        lines.push(Line::from(format!("Email: {}", contact.email)));
+        lines.push(Line::from(format!("Phone: {}", contact.phone)));
+        lines.push(Line::from(format!("Location: {}", contact.location)));
         if let Some(website) = &data_manager.resume.website {
             lines.push(Line::from(format!("Website: {}", website)));
         }
+        for profile in &contact.profiles {
+            lines.push(Line::from(profile_label(profile)));
+        }
         lines.push(Line::from(""));
         if focus == &Focus::Content {
             lines.push(Line::from(Span::styled("Press Enter to edit", Style::default().fg(Color::Magenta))));
@@ -109,7 +166,7 @@ impl ContentPane {
         lines
     }
 
-    fn render_education(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line> {
+    fn render_education(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line<'_>> {
         let mut lines = vec![
             Line::from(""),
         ];
@@ -134,9 +191,12 @@ impl ContentPane {
                 
                 if is_selected && focus == &Focus::Content {
                     lines.push(Line::from(format!("   Degree: {}", education.degree)));
-                    lines.push(Line::from(format!("   Years: {}", education.years)));
+                    lines.push(Line::from(format!("   Dates: {}", education.dates)));
+                    if !education.tags.is_empty() {
+                        lines.push(Line::from(format!("   Tags: {}", education.tags.join(", "))));
+                    }
                     for point in &education.points {
-                        lines.push(Line::from(format!("   • {}", point)));
+                        lines.push(Line::from(format!("   • {}", point_label(point))));
                     }
                     lines.push(Line::from(""));
                 }
@@ -156,7 +216,7 @@ impl ContentPane {
                     add_new_style,
                 )));
                 lines.push(Line::from(""));
-                lines.push(Line::from(Span::styled("Use ↑/↓ to select, Enter to edit", Style::default().fg(Color::Cyan))));
+                lines.push(Line::from(Span::styled("Use ↑/↓ to select, Enter to edit, K/J to move, c to duplicate, d to delete", Style::default().fg(Color::Cyan))));
             }
             
         }
@@ -164,7 +224,7 @@ impl ContentPane {
         lines
     }
 
-    fn render_experience(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line> {
+    fn render_experience(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line<'_>> {
         let mut lines = vec![
             Line::from(""),
         ];
@@ -190,9 +250,12 @@ impl ContentPane {
                 if is_selected && focus == &Focus::Content {
                     lines.push(Line::from(format!("   Company: {}", experience.company)));
                     lines.push(Line::from(format!("   Position: {}", experience.position)));
-                    lines.push(Line::from(format!("   Duration: {}", experience.years)));
+                    lines.push(Line::from(format!("   Dates: {}", experience.dates)));
+                    if !experience.tags.is_empty() {
+                        lines.push(Line::from(format!("   Tags: {}", experience.tags.join(", "))));
+                    }
                     for point in &experience.points {
-                        lines.push(Line::from(format!("   • {}", point)));
+                        lines.push(Line::from(format!("   • {}", point_label(point))));
                     }
                     lines.push(Line::from(""));
                 }
@@ -212,13 +275,13 @@ impl ContentPane {
                     add_new_style,
                 )));
                 lines.push(Line::from(""));
-                lines.push(Line::from(Span::styled("Use ↑/↓ to select, Enter to edit", Style::default().fg(Color::Cyan))));
+                lines.push(Line::from(Span::styled("Use ↑/↓ to select, Enter to edit, K/J to move, c to duplicate, d to delete", Style::default().fg(Color::Cyan))));
             }
         }
         lines
     }
 
-    fn render_projects(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line> {
+    fn render_projects(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line<'_>> {
         let mut lines = vec![
             Line::from(""),
         ];
@@ -245,8 +308,11 @@ impl ContentPane {
                     if let Some(link) = &project.link {
                         lines.push(Line::from(format!("   Link: {}", link)));
                     }
+                    if !project.tags.is_empty() {
+                        lines.push(Line::from(format!("   Tags: {}", project.tags.join(", "))));
+                    }
                     for point in &project.points {
-                        lines.push(Line::from(format!("   • {}", point)));
+                        lines.push(Line::from(format!("   • {}", point_label(point))));
                     }
                     lines.push(Line::from(""));
                 }
@@ -265,89 +331,240 @@ impl ContentPane {
                     add_new_style,
                 )));
                 lines.push(Line::from(""));
-                lines.push(Line::from(Span::styled("Use ↑/↓ to select, Enter to edit", Style::default().fg(Color::Cyan))));
+                lines.push(Line::from(Span::styled("Use ↑/↓ to select, Enter to edit, K/J to move, c to duplicate, d to delete", Style::default().fg(Color::Cyan))));
             }
         }
 
         lines
     }
 
-    fn render_skills(&self, focus: &Focus) -> Vec<Line> {
-        vec![
-            Line::from(""),
-            Line::from("List your technical skills:"),
-            Line::from("• Programming Languages: [List languages]"),
-            Line::from("• Frameworks: [Web frameworks, libraries]"),
-            Line::from("• Tools: [Development tools, IDEs]"),
-            Line::from("• Databases: [Database technologies]"),
-            Line::from("• Other: [Certifications, soft skills]"),
+    fn render_skills(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line<'_>> {
+        let mut lines = vec![
             Line::from(""),
-            if focus == &Focus::Content {
-                Line::from(Span::styled("Press Enter to add/edit skills", Style::default().fg(Color::Yellow)))
+        ];
+
+        if data_manager.resume.skills.is_empty() {
+            lines.push(Line::from("No skill categories found."));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Press Enter to add skills", Style::default().fg(Color::Yellow))));
+        } else {
+            for (i, category) in data_manager.resume.skills.iter().enumerate() {
+                let is_selected = self.selected_entry == Some(i);
+                let style = if is_selected && focus == &Focus::Content {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+
+                lines.push(Line::from(Span::styled(
+                    format!("{}. {}", i + 1, category.name),
+                    style,
+                )));
+
+                if is_selected && focus == &Focus::Content {
+                    if !category.tags.is_empty() {
+                        lines.push(Line::from(format!("   Tags: {}", category.tags.join(", "))));
+                    }
+                    for skill in &category.items {
+                        lines.push(Line::from(format!("   • {}", skill_label(skill))));
+                    }
+                    lines.push(Line::from(""));
+                }
+            }
+
+            let add_new_selected = self.selected_entry == Some(data_manager.resume.skills.len());
+            let add_new_style = if add_new_selected {
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
             } else {
-                Line::from("")
+                Style::default().fg(Color::Green)
+            };
+
+            if focus == &Focus::Content {
+                lines.push(Line::from(Span::styled(
+                    format!("{}. + Add new skill category", data_manager.resume.skills.len() + 1),
+                    add_new_style,
+                )));
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled("Use ↑/↓ to select, Enter to edit, K/J to move, c to duplicate, d to delete", Style::default().fg(Color::Cyan))));
             }
-        ]
+        }
+
+        lines
     }
 
-    fn render_export(&self, focus: &Focus) -> Vec<Line> {
+    fn render_export(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line<'_>> {
+        let format = self.export_format;
+        let profile = match self.export_profile.as_deref().map(|name| data_manager.profile(name)) {
+            Some(Ok(profile)) => {
+                let mut summary: Vec<&str> = profile.sections().iter().map(|s| s.name()).collect();
+                let tags = format!("tags: {}", profile.tags.join(", "));
+                if !profile.tags.is_empty() {
+                    summary.push(&tags);
+                }
+                format!("Profile: {} ({})", profile.name, summary.join(", "))
+            }
+            Some(Err(e)) => format!("Profile: {}", e),
+            None => "Profile: everything".to_string(),
+        };
         let mut lines = vec![
             Line::from(""),
-            Line::from("Export your resume to LaTeX format"),
-            Line::from(""),
-            Line::from("Export location: ./output/resume.tex"),
+            Line::from(format!("Export your resume to {} format", format.title())),
             Line::from(""),
+            Line::from(format!("Template: {}", TemplateSource::default_for(format).describe())),
+            Line::from(profile),
+            Line::from(format!(
+                "Export location: {}",
+                format.default_output_for(self.export_profile.as_deref()).display()
+            )),
         ];
 
+        if format == Format::Latex {
+            lines.push(Line::from(match self.pdf_engine {
+                Some(engine) if self.pdf_engine_found => format!("PDF: compile with {}", engine),
+                Some(engine) => format!("PDF: compile with {} (not found on PATH)", engine),
+                None => "PDF: off".to_string(),
+            }));
+        }
+        lines.push(Line::from(""));
+
+        if focus == &Focus::Content {
+            let keys = if format == Format::Latex {
+                "f to change format, v to change profile, p to toggle PDF, e to change LaTeX engine, a to export all"
+            } else {
+                "f to change format, v to change profile, a to export all profiles and formats"
+            };
+            lines.push(Line::from(Span::styled(keys, Style::default().fg(Color::Cyan))));
+        }
+
         if let Some(status) = &self.export_status {
+            let (message, color) = match status {
+                Status::Success(message) => (message, Color::Green),
+                Status::Error(message) => (message, Color::Red),
+            };
             lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
-                format!("Status: {}", status),
-                Style::default().fg(Color::Green),
-            )));
-        } else {
-            if focus == &Focus::Content {
-                lines.push(Line::from(Span::styled("Press Enter to export resume", Style::default().fg(Color::Yellow))));
+            for (i, text) in message.lines().enumerate() {
+                let text = if i == 0 { format!("Status: {}", text) } else { text.to_string() };
+                lines.push(Line::from(Span::styled(text, Style::default().fg(color))));
             }
+        } else if focus == &Focus::Content {
+            lines.push(Line::from(Span::styled("Press Enter to export resume", Style::default().fg(Color::Yellow))));
+        }
+
+        lines
+    }
+
+    fn render_check(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line<'_>> {
+        let mut lines = vec![
+            Line::from(""),
+        ];
+
+        let issues = data_manager.validate();
+        if issues.is_empty() {
+            lines.push(Line::from(Span::styled("No problems found.", Style::default().fg(Color::Green))));
+            return lines;
+        }
+
+        let errors = issues.iter().filter(|i| i.severity == Severity::Error).count();
+        lines.push(Line::from(format!("{} error(s), {} warning(s)", errors, issues.len() - errors)));
+        lines.push(Line::from(""));
+
+        for (i, issue) in issues.iter().enumerate() {
+            let color = match issue.severity {
+                Severity::Error => Color::Red,
+                Severity::Warning => Color::Yellow,
+            };
+            let style = if self.selected_entry == Some(i) && focus == &Focus::Content {
+                Style::default().fg(color).add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::default().fg(color)
+            };
+            lines.push(Line::from(Span::styled(issue.to_string(), style)));
+        }
+
+        if focus == &Focus::Content {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Press Enter to go to the selected entry", Style::default().fg(Color::Magenta))));
+        }
+
+        lines
+    }
+
+    fn render_backups(&self, data_manager: &DataManager, focus: &Focus) -> Vec<Line<'_>> {
+        let mut lines = vec![
+            Line::from(""),
+        ];
+
+        let backups = data_manager.backups();
+        if backups.is_empty() {
+            lines.push(Line::from("No backups yet. One is taken every time the resume is saved."));
+            return lines;
+        }
+
+        for (i, backup) in backups.iter().enumerate() {
+            let is_selected = self.selected_entry == Some(i);
+            let style = if is_selected && focus == &Focus::Content {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            lines.push(Line::from(Span::styled(format!("{}. {}", i + 1, backup.label()), style)));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("Keeping the newest {} next to {}", data_manager.keep_backups, data_manager.file_path)));
+        if focus == &Focus::Content {
+            lines.push(Line::from(Span::styled("Press Enter to restore the selected backup", Style::default().fg(Color::Magenta))));
         }
 
         lines
     }
 
-    fn render_exit(&self) -> Vec<Line> {
+    fn render_exit(&self) -> Vec<Line<'_>> {
         vec![
             Line::from(""),
             Line::from(Span::styled("Press Enter to exit", Style::default().fg(Color::Red))),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Edits stay in memory until saved: Ctrl+S save, Ctrl+O save as, Ctrl+R reload from disk",
+                Style::default().fg(Color::Cyan),
+            )),
         ]
     }
 
-    pub fn enter_edit_mode(&mut self, menu: &MenuItem) {
+    pub fn enter_edit_mode(&mut self, menu: &MenuItem, data_manager: &DataManager) {
         self.is_editing = true;
         self.current_field = 0;
-        self.cursor_position = 0;
-        self.fields = self.get_default_fields(menu);
+        self.fields = self.get_current_fields(menu, data_manager).into_iter().map(TextInput::new).collect();
+        self.reset_rows(menu);
+        if *menu == MenuItem::PersonalInfo {
+            self.profiles = Some(RowEditor::new(data_manager.resume.contact.profiles.clone()));
+        }
         self.selected_entry = None;
         self.entry_type = None;
         self.current_menu = Some(menu.clone());
+        self.status = None;
+        self.remember_original();
     }
 
     pub fn enter_entry_edit_mode(&mut self, menu: &MenuItem, entry_idx: usize, data_manager: &DataManager) {
         self.is_editing = true;
         self.current_field = 0;
-        self.cursor_position = 0;
         self.selected_entry = Some(entry_idx);
         self.current_menu = Some(menu.clone());
-        
-        
-        self.fields = match menu {
+        self.status = None;
+        self.reset_rows(menu);
+
+        let fields = match menu {
             MenuItem::Education => {
                 if let Some(education) = data_manager.resume.education.get(entry_idx) {
                     self.entry_type = Some(EntryType::Education(entry_idx));
+                    self.points = Some(RowEditor::new(education.points.clone()));
                     vec![
                         education.name.clone(),
                         education.degree.clone(),
-                        education.years.clone(),
-                        education.points.join("; "),
+                        education.dates.to_string(),
+                        String::new(),
+                        education.tags.join(", "),
                     ]
                 } else {
                     self.get_default_fields(menu)
@@ -356,11 +573,13 @@ impl ContentPane {
             MenuItem::Experience => {
                 if let Some(experience) = data_manager.resume.experience.get(entry_idx) {
                     self.entry_type = Some(EntryType::Experience(entry_idx));
+                    self.points = Some(RowEditor::new(experience.points.clone()));
                     vec![
                         experience.company.clone(),
                         experience.position.clone(),
-                        experience.years.clone(),
-                        experience.points.join("; "),
+                        experience.dates.to_string(),
+                        String::new(),
+                        experience.tags.join(", "),
                     ]
                 } else {
                     self.get_default_fields(menu)
@@ -369,10 +588,25 @@ impl ContentPane {
             MenuItem::Projects => {
                 if let Some(project) = data_manager.resume.projects.get(entry_idx) {
                     self.entry_type = Some(EntryType::Project(entry_idx));
+                    self.points = Some(RowEditor::new(project.points.clone()));
                     vec![
                         project.name.clone(),
                         project.link.clone().unwrap_or_default(),
-                        project.points.join("; "),
+                        String::new(),
+                        project.tags.join(", "),
+                    ]
+                } else {
+                    self.get_default_fields(menu)
+                }
+            }
+            MenuItem::Skills => {
+                if let Some(category) = data_manager.resume.skills.get(entry_idx) {
+                    self.entry_type = Some(EntryType::Skill(entry_idx));
+                    self.skills = Some(RowEditor::new(category.items.clone()));
+                    vec![
+                        category.name.clone(),
+                        String::new(),
+                        category.tags.join(", "),
                     ]
                 } else {
                     self.get_default_fields(menu)
@@ -380,19 +614,48 @@ impl ContentPane {
             }
             _ => self.get_default_fields(menu),
         };
+        self.fields = fields.into_iter().map(TextInput::new).collect();
+        self.remember_original();
+    }
+
+    /// Starts empty row editors for the lists `menu`'s form has.
+    fn reset_rows(&mut self, menu: &MenuItem) {
+        self.points = None;
+        self.skills = None;
+        self.profiles = None;
+        match menu {
+            MenuItem::Skills => self.skills = Some(RowEditor::default()),
+            MenuItem::PersonalInfo => self.profiles = Some(RowEditor::default()),
+            _ if rows_field(menu).is_some() => self.points = Some(RowEditor::default()),
+            _ => {}
+        }
+    }
+
+    fn remember_original(&mut self) {
+        self.original_fields = self.fields.iter().map(|f| f.value().to_string()).collect();
+        self.original_points = self.edited_points();
+        self.original_skills = self.edited_skills();
+        self.original_profiles = self.edited_profiles();
+    }
+
+    /// Whether the open form differs from what it was opened with.
+    pub fn form_changed(&self) -> bool {
+        self.is_editing
+            && (self.fields.iter().map(TextInput::value).ne(self.original_fields.iter().map(String::as_str))
+                || self.edited_points() != self.original_points
+                || self.edited_skills() != self.original_skills
+                || self.edited_profiles() != self.original_profiles)
     }
 
     pub fn next_field(&mut self) {
         if self.is_editing && self.current_field < self.fields.len() - 1 {
             self.current_field += 1;
-            self.cursor_position = self.fields[self.current_field].len();
         }
     }
 
     pub fn previous_field(&mut self) {
         if self.is_editing && self.current_field > 0 {
             self.current_field -= 1;
-            self.cursor_position = self.fields[self.current_field].len();
         }
     }
 
@@ -401,6 +664,9 @@ impl ContentPane {
             MenuItem::Education => data_manager.resume.education.len(),
             MenuItem::Experience => data_manager.resume.experience.len(),
             MenuItem::Projects => data_manager.resume.projects.len(),
+            MenuItem::Skills => data_manager.resume.skills.len(),
+            MenuItem::Check => data_manager.validate().len(),
+            MenuItem::Backups => data_manager.backups().len(),
             _ => 0,
         };
 
@@ -416,6 +682,9 @@ impl ContentPane {
             MenuItem::Education => data_manager.resume.education.len() + 1, 
             MenuItem::Experience => data_manager.resume.experience.len() + 1,
             MenuItem::Projects => data_manager.resume.projects.len() + 1,
+            MenuItem::Skills => data_manager.resume.skills.len() + 1,
+            MenuItem::Check => data_manager.validate().len(),
+            MenuItem::Backups => data_manager.backups().len(),
             _ => 0,
         };
 
@@ -432,6 +701,9 @@ impl ContentPane {
             MenuItem::Education => data_manager.resume.education.len() + 1, 
             MenuItem::Experience => data_manager.resume.experience.len() + 1,
             MenuItem::Projects => data_manager.resume.projects.len() + 1,
+            MenuItem::Skills => data_manager.resume.skills.len() + 1,
+            MenuItem::Check => data_manager.validate().len(),
+            MenuItem::Backups => data_manager.backups().len(),
             _ => 0,
         };
 
@@ -445,14 +717,21 @@ impl ContentPane {
 
     pub fn handle_enter(&mut self) -> bool {
         if self.is_editing {
-            if self.current_field < self.fields.len() - 1 {
+            if self.on_rows_field() && (self.points.is_some() || self.skills.is_some() || self.profiles.is_some()) {
+                if let Some(points) = &mut self.points {
+                    points.open();
+                }
+                if let Some(skills) = &mut self.skills {
+                    skills.open();
+                }
+                if let Some(profiles) = &mut self.profiles {
+                    profiles.open();
+                }
+                false
+            } else if self.current_field < self.fields.len() - 1 {
                 self.next_field();
                 false 
             } else {
-                
-                self.is_editing = false;
-                self.selected_entry = None;
-                self.entry_type = None;
                 true 
             }
         } else {
@@ -460,120 +739,125 @@ impl ContentPane {
         }
     }
 
-    pub fn save_edited_data(&mut self, data_manager: &mut DataManager) -> Result<(), String> {
+    pub fn finish_edit(&mut self) {
+        self.is_editing = false;
+        self.points = None;
+        self.skills = None;
+        self.profiles = None;
+        self.selected_entry = None;
+        self.entry_type = None;
+    }
+
+    pub fn save_edited_data(&mut self, data_manager: &mut DataManager) -> Result<(), DataError> {
         if !self.is_editing {
             return Ok(());
         }
+        let fields: Vec<&str> = self.fields.iter().map(TextInput::value).collect();
 
         match &self.entry_type {
             Some(EntryType::Education(idx)) => {
-                if self.fields.len() >= 4 {
-                    let points: Vec<String> = self.fields[3]
-                        .split(';')
-                        .map(|s| s.trim().to_string())
-                        .filter(|s| !s.is_empty())
-                        .collect();
-                    
+                if fields.len() >= 5 {
                     let education = crate::data::manager::Education {
-                        name: self.fields[0].clone(),
-                        degree: self.fields[1].clone(),
-                        years: self.fields[2].clone(),
-                        points,
+                        name: fields[0].to_string(),
+                        degree: fields[1].to_string(),
+                        dates: DateRange::parse(fields[2]),
+                        points: self.edited_points(),
+                        tags: parse_tags(fields[4]),
                     };
                     
                     data_manager.update_education(*idx, education)?;
                 }
             }
             Some(EntryType::Experience(idx)) => {
-                if self.fields.len() >= 4 {
-                    let points: Vec<String> = self.fields[3]
-                        .split(';')
-                        .map(|s| s.trim().to_string())
-                        .filter(|s| !s.is_empty())
-                        .collect();
-                    
+                if fields.len() >= 5 {
                     let experience = crate::data::manager::Experience {
-                        company: self.fields[0].clone(),
-                        position: self.fields[1].clone(),
-                        years: self.fields[2].clone(),
-                        points,
+                        company: fields[0].to_string(),
+                        position: fields[1].to_string(),
+                        dates: DateRange::parse(fields[2]),
+                        points: self.edited_points(),
+                        tags: parse_tags(fields[4]),
                     };
                     
                     data_manager.update_experience(*idx, experience)?;
                 }
             }
             Some(EntryType::Project(idx)) => {
-                if self.fields.len() >= 3 {
-                    let points: Vec<String> = self.fields[2]
-                        .split(';')
-                        .map(|s| s.trim().to_string())
-                        .filter(|s| !s.is_empty())
-                        .collect();
-                    
+                if fields.len() >= 4 {
                     let project = crate::data::manager::Project {
-                        name: self.fields[0].clone(),
-                        link: if self.fields[1].is_empty() { None } else { Some(self.fields[1].clone()) },
-                        points,
+                        name: fields[0].to_string(),
+                        link: if fields[1].is_empty() { None } else { Some(fields[1].to_string()) },
+                        points: self.edited_points(),
+                        tags: parse_tags(fields[3]),
                     };
                     
                     data_manager.update_project(*idx, project)?;
                 }
             }
+            Some(EntryType::Skill(idx)) => {
+                if fields.len() >= 3 {
+                    let category = SkillCategory {
+                        name: fields[0].to_string(),
+                        items: self.edited_skills(),
+                        tags: parse_tags(fields[2]),
+                    };
+
+                    data_manager.update_skill_category(*idx, category)?;
+                }
+            }
             None => {
                 
                 match self.get_current_menu_type() {
-                    Some(MenuItem::Education) => {
-                        if self.fields.len() >= 4 {
-                            let points: Vec<String> = self.fields[3]
-                                .split(';')
-                                .map(|s| s.trim().to_string())
-                                .filter(|s| !s.is_empty())
-                                .collect();
-                            
-                            let education = crate::data::manager::Education {
-                                name: self.fields[0].clone(),
-                                degree: self.fields[1].clone(),
-                                years: self.fields[2].clone(),
-                                points,
-                            };
-                            
-                            data_manager.add_education(education);
-                        }
+                    Some(MenuItem::Education) if fields.len() >= 5 => {
+                        let education = crate::data::manager::Education {
+                            name: fields[0].to_string(),
+                            degree: fields[1].to_string(),
+                            dates: DateRange::parse(fields[2]),
+                            points: self.edited_points(),
+                            tags: parse_tags(fields[4]),
+                        };
+                        
+                        data_manager.add_education(education)?;
                     }
-                    Some(MenuItem::Experience) => {
-                        if self.fields.len() >= 4 {
-                            let points: Vec<String> = self.fields[3]
-                                .split(';')
-                                .map(|s| s.trim().to_string())
-                                .filter(|s| !s.is_empty())
-                                .collect();
-                            
-                            let experience = crate::data::manager::Experience {
-                                company: self.fields[0].clone(),
-                                position: self.fields[1].clone(),
-                                years: self.fields[2].clone(),
-                                points,
-                            };
-                            
-                            data_manager.add_experience(experience);
-                        }
+                    Some(MenuItem::Experience) if fields.len() >= 5 => {
+                        let experience = crate::data::manager::Experience {
+                            company: fields[0].to_string(),
+                            position: fields[1].to_string(),
+                            dates: DateRange::parse(fields[2]),
+                            points: self.edited_points(),
+                            tags: parse_tags(fields[4]),
+                        };
+                        
+                        data_manager.add_experience(experience)?;
                     }
-                    Some(MenuItem::Projects) => {
-                        if self.fields.len() >= 3 {
-                            let points: Vec<String> = self.fields[2]
-                                .split(';')
-                                .map(|s| s.trim().to_string())
-                                .filter(|s| !s.is_empty())
-                                .collect();
-                            
-                            let project = crate::data::manager::Project {
-                                name: self.fields[0].clone(),
-                                link: if self.fields[1].is_empty() { None } else { Some(self.fields[1].clone()) },
-                                points,
-                            };
-                            
-                            data_manager.add_project(project);
-                        }
+                    Some(MenuItem::Projects) if fields.len() >= 4 => {
+                        let project = crate::data::manager::Project {
+                            name: fields[0].to_string(),
+                            link: if fields[1].is_empty() { None } else { Some(fields[1].to_string()) },
+                            points: self.edited_points(),
+                            tags: parse_tags(fields[3]),
+                        };
+                        
+                        data_manager.add_project(project)?;
+                    }
+                    Some(MenuItem::PersonalInfo) if fields.len() >= 6 => {
+                        let contact = Contact {
+                            email: fields[1].trim().to_string(),
+                            phone: fields[2].trim().to_string(),
+                            location: fields[3].trim().to_string(),
+                            profiles: self.edited_profiles(),
+                        };
+                        let website = if fields[5].trim().is_empty() { None } else { Some(fields[5].trim().to_string()) };
+
+                        data_manager.update_personal_info(fields[0].trim().to_string(), contact, website)?;
+                    }
+                    Some(MenuItem::Skills) if fields.len() >= 3 => {
+                        let category = SkillCategory {
+                            name: fields[0].to_string(),
+                            items: self.edited_skills(),
+                            tags: parse_tags(fields[2]),
+                        };
+
+                        data_manager.add_skill_category(category)?;
                     }
                     _ => {}
                 }
@@ -587,17 +871,67 @@ impl ContentPane {
         self.current_menu.clone()
     }
 
-    pub fn handle_text_input(&mut self, c: char) {
-        if self.is_editing && self.current_field < self.fields.len() {
-            self.fields[self.current_field].insert(self.cursor_position, c);
-            self.cursor_position += 1;
+    /// Whether a row editor is open and should get the keyboard.
+    pub fn rows_active(&self) -> bool {
+        self.is_editing
+            && (self.points.as_ref().is_some_and(|p| p.active)
+                || self.skills.as_ref().is_some_and(|s| s.active)
+                || self.profiles.as_ref().is_some_and(|p| p.active))
+    }
+
+    /// Keeps whatever is being typed into a row editor.
+    pub fn confirm_rows(&mut self) {
+        if let Some(points) = &mut self.points {
+            points.confirm();
+        }
+        if let Some(skills) = &mut self.skills {
+            skills.confirm();
         }
+        if let Some(profiles) = &mut self.profiles {
+            profiles.confirm();
+        }
+    }
+
+    fn on_rows_field(&self) -> bool {
+        self.current_menu.as_ref().and_then(rows_field) == Some(self.current_field)
+    }
+
+    fn edited_points(&self) -> Vec<Point> {
+        self.points.as_ref().map(|p| p.rows().to_vec()).unwrap_or_default()
+    }
+
+    fn edited_skills(&self) -> Vec<Skill> {
+        self.skills.as_ref().map(|s| s.rows().to_vec()).unwrap_or_default()
+    }
+
+    fn edited_profiles(&self) -> Vec<Profile> {
+        self.profiles.as_ref().map(|p| p.rows().to_vec()).unwrap_or_default()
     }
 
-    pub fn handle_backspace(&mut self) {
-        if self.is_editing && self.current_field < self.fields.len() && self.cursor_position > 0 {
-            self.fields[self.current_field].remove(self.cursor_position - 1);
-            self.cursor_position -= 1;
+    /// Passes an editing key to the current field. Returns false if the
+    /// field did not use it.
+    pub fn handle_field_key(&mut self, key: KeyEvent) -> bool {
+        if !self.is_editing || self.on_rows_field() {
+            return false;
+        }
+        match self.fields.get_mut(self.current_field) {
+            Some(field) => field.handle_key(key),
+            None => false,
+        }
+    }
+
+    fn get_current_fields(&self, menu: &MenuItem, data_manager: &DataManager) -> Vec<String> {
+        let resume = &data_manager.resume;
+        match menu {
+            MenuItem::PersonalInfo => vec![
+                resume.name.clone(),
+                resume.contact.email.clone(),
+                resume.contact.phone.clone(),
+                resume.contact.location.clone(),
+                String::new(),
+                resume.website.clone().unwrap_or_default(),
+            ],
+            _ => self.get_default_fields(menu),
         }
     }
 
@@ -616,33 +950,34 @@ impl ContentPane {
                 "".to_string(), 
                 "".to_string(), 
                 "".to_string(), 
+                "".to_string(), 
             ],
             MenuItem::Experience => vec![
                 "".to_string(), 
                 "".to_string(), 
                 "".to_string(), 
                 "".to_string(), 
+                "".to_string(), 
             ],
             MenuItem::Projects => vec![
                 "".to_string(), 
                 "".to_string(), 
                 "".to_string(), 
+                "".to_string(), 
             ],
             MenuItem::Skills => vec![
                 "".to_string(), 
                 "".to_string(), 
                 "".to_string(), 
-                "".to_string(), 
-                "".to_string(), 
             ],
             _ => vec!["".to_string()],
         }
     }
 
-    fn render_editing_form(&self, menu: &MenuItem) -> Vec<Line> {
+    fn render_editing_form(&self, menu: &MenuItem, width: usize) -> Vec<Line<'_>> {
         let field_labels = self.get_field_labels(menu);
         let mut lines = vec![
-            Line::from("Edit Mode - Use ↑/↓ to navigate fields, type to edit, Enter to save"),
+            Line::from("Edit Mode - Use ↑/↓ to navigate fields, type to edit, Enter on the last field to apply"),
             Line::from(""),
         ];
 
@@ -654,29 +989,34 @@ impl ContentPane {
                 Style::default().fg(Color::White)
             };
 
-            let cursor_char = if is_current && self.cursor_position < value.len() {
-                "|"
-            } else if is_current {
-                "_"
-            } else {
-                " "
-            };
-
-            let display_value = if value.is_empty() {
-                format!("[Enter {}]", label)
-            } else {
-                value.clone()
-            };
+            if rows_field(menu) == Some(i) {
+                let rows = match (&self.points, &self.skills, &self.profiles) {
+                    (Some(points), _, _) => Some(row_lines(points, label, is_current, style, width)),
+                    (_, Some(skills), _) => Some(row_lines(skills, label, is_current, style, width)),
+                    (_, _, Some(profiles)) => Some(row_lines(profiles, label, is_current, style, width)),
+                    _ => None,
+                };
+                if let Some(rows) = rows {
+                    lines.extend(rows);
+                    continue;
+                }
+            }
 
-            lines.push(Line::from(Span::styled(
-                format!("{}: {}{}", label, display_value, cursor_char),
-                style,
-            )));
+            let prefix = format!("{}: ", label);
+            if value.is_empty() && !is_current {
+                lines.push(Line::from(Span::styled(format!("{}[Enter {}]", prefix, label), style)));
+                continue;
+            }
+            // Long values scroll sideways instead of wrapping.
+            let field_width = width.saturating_sub(prefix.width());
+            let mut spans = vec![Span::styled(prefix, style)];
+            spans.extend(value.render(field_width, is_current, style));
+            lines.push(Line::from(spans));
         }
 
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "Press Tab to switch back to sidebar, Enter to save",
+            "Press Tab to switch back to sidebar, Esc to cancel, Ctrl+S to apply and save",
             Style::default().fg(Color::Cyan),
         )));
 
@@ -685,32 +1025,107 @@ impl ContentPane {
 
     fn get_field_labels(&self, menu: &MenuItem) -> Vec<&'static str> {
         match menu {
-            MenuItem::PersonalInfo => vec!["Name", "Email", "Phone", "Location", "LinkedIn", "GitHub"],
-            MenuItem::Education => vec!["Institution", "Degree", "Years", "Points (semicolon separated)"],
-            MenuItem::Experience => vec!["Company", "Position", "Duration", "Points (semicolon separated)"],
-            MenuItem::Projects => vec!["Project Name", "Link", "Points (semicolon separated)"],
-            MenuItem::Skills => vec!["Programming Languages", "Frameworks", "Tools", "Databases", "Other"],
+            MenuItem::PersonalInfo => vec!["Name", "Email", "Phone", "Location", "Profiles", "Website"],
+            MenuItem::Education => vec!["Institution", "Degree", "Dates (e.g. Aug 2021 - May 2025)", "Points", "Tags (comma separated)"],
+            MenuItem::Experience => vec!["Company", "Position", "Dates (e.g. Jan 2022 - Present)", "Points", "Tags (comma separated)"],
+            MenuItem::Projects => vec!["Project Name", "Link", "Points", "Tags (comma separated)"],
+            MenuItem::Skills => vec!["Category", "Skills", "Tags (comma separated)"],
             _ => vec!["Field"],
         }
     }
 
-    pub fn show_export(&mut self) {
-        self.content = "Exporting resume...".to_string();
+    pub fn trigger_export(&mut self) {
+        self.export_status = Some(Status::Success("Exporting...".to_string()));
     }
 
-    pub fn show_form(&mut self, menu: &MenuItem) {
-        self.content = format!("Editing {} form...", menu.title());
+    pub fn set_export_status(&mut self, status: Status) {
+        self.export_status = Some(status);
     }
 
-    pub fn trigger_export(&mut self) {
-        self.export_status = Some("Exporting...".to_string());
+    pub fn next_export_format(&mut self) {
+        self.export_format = self.export_format.next();
+        self.export_status = None;
     }
 
-    pub fn set_export_status(&mut self, status: String) {
-        self.export_status = Some(status);
+    /// Cycles through everything, then each profile in `profiles`.
+    pub fn next_export_profile(&mut self, profiles: &[ResumeProfile]) {
+        let next = match &self.export_profile {
+            None => 0,
+            Some(name) => profiles.iter().position(|p| &p.name == name).map_or(profiles.len(), |i| i + 1),
+        };
+        self.export_profile = profiles.get(next).map(|p| p.name.clone());
+        self.export_status = None;
     }
 
-    pub fn clear_export_status(&mut self) {
-        self.export_status = None;
+    pub fn toggle_pdf(&mut self) {
+        match self.pdf_engine {
+            Some(_) => self.pdf_engine = None,
+            None => self.set_pdf_engine(Engine::detect().unwrap_or(Engine::Xelatex)),
+        }
+    }
+
+    pub fn next_pdf_engine(&mut self) {
+        self.set_pdf_engine(self.pdf_engine.map_or(Engine::Xelatex, |e| e.next()));
+    }
+
+    fn set_pdf_engine(&mut self, engine: Engine) {
+        self.pdf_engine = Some(engine);
+        self.pdf_engine_found = engine.is_installed();
+    }
+
+    pub fn set_status(&mut self, status: Status) {
+        self.status = Some(status);
+    }
+}
+
+fn skill_label(skill: &Skill) -> String {
+    match &skill.proficiency {
+        Some(level) => format!("{} ({})", skill.name, level),
+        None => skill.name.clone(),
+    }
+}
+
+/// Index of the row in `menu`'s edit form that opens a row editor: the
+/// bullet points, a category's skills or the contact's profile links.
+fn rows_field(menu: &MenuItem) -> Option<usize> {
+    match menu {
+        MenuItem::PersonalInfo => Some(4),
+        MenuItem::Education | MenuItem::Experience => Some(3),
+        MenuItem::Projects => Some(2),
+        MenuItem::Skills => Some(1),
+        _ => None,
+    }
+}
+
+/// The collapsed form row for a row editor, followed by its rows.
+fn row_lines<'a, T: Row>(rows: &'a RowEditor<T>, label: &str, is_current: bool, style: Style, width: usize) -> Vec<Line<'a>> {
+    let hint = if is_current && !rows.active { " (Enter to edit)" } else { "" };
+    let mut lines = vec![Line::from(Span::styled(format!("{}: {}{}", label, rows.summary(), hint), style))];
+    lines.extend(rows.render(width));
+    lines
+}
+
+fn point_label(point: &Point) -> String {
+    if point.tags.is_empty() {
+        point.text.clone()
+    } else {
+        format!("{} [{}]", point.text, point.tags.join(", "))
+    }
+}
+
+fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn profile_label(profile: &Profile) -> String {
+    if profile.label.is_empty() {
+        profile.url.clone()
+    } else {
+        format!("{}: {}", profile.label, profile.url)
     }
 }
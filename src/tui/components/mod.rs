@@ -1,5 +1,15 @@
 pub mod sidebar;
+pub mod confirm;
 pub mod content_pane;
+pub mod row_editor;
+pub mod prompt;
+pub mod recovery;
+pub mod text_input;
 
 pub use sidebar::Sidebar;
+pub use confirm::ConfirmDialog;
 pub use content_pane::ContentPane;
+pub use row_editor::RowEditor;
+pub use prompt::PromptDialog;
+pub use recovery::RecoveryScreen;
+pub use text_input::TextInput;
@@ -0,0 +1,69 @@
+use crate::tui::components::confirm::centered;
+use crate::tui::components::text_input::TextInput;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// What the entered text is used for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptAction {
+    SaveAs,
+}
+
+/// Asks for one line of text, drawn over the rest of the screen.
+#[derive(Debug)]
+pub struct PromptDialog {
+    pub title: String,
+    pub action: PromptAction,
+    input: TextInput,
+}
+
+impl PromptDialog {
+    pub fn new(title: impl Into<String>, value: impl Into<String>, action: PromptAction) -> Self {
+        Self {
+            title: title.into(),
+            action,
+            input: TextInput::new(value),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        self.input.value()
+    }
+
+    /// Passes an editing key to the input. Returns false if it was not used.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.input.handle_key(key)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let area = centered(area, 60, 6);
+        // Two columns go to the borders and one to the left padding.
+        let width = usize::from(area.width.saturating_sub(3));
+        let mut input = vec![Span::raw(" ")];
+        input.extend(self.input.render(width, true, Style::default().fg(Color::White)));
+        let lines = vec![
+            Line::from(""),
+            Line::from(input),
+            Line::from(""),
+            Line::from(Span::styled("Enter to confirm, Esc to cancel", Style::default().fg(Color::Cyan))),
+        ];
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(self.title.as_str())
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Yellow)),
+            )
+            .alignment(Alignment::Left);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(paragraph, area);
+    }
+}
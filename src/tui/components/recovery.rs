@@ -0,0 +1,110 @@
+use crate::data::backup::Backup;
+use crate::data::DataError;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecoveryAction {
+    OpenAnyway,
+    RestoreBackup(Backup),
+    StartFromTemplate,
+    Quit,
+}
+
+impl RecoveryAction {
+    pub fn title(&self) -> String {
+        match self {
+            RecoveryAction::OpenAnyway => "Open anyway (skip sections that fail to load)".to_string(),
+            RecoveryAction::RestoreBackup(backup) => format!("Restore the backup from {}", backup.label()),
+            RecoveryAction::StartFromTemplate => "Start from the template".to_string(),
+            RecoveryAction::Quit => "Quit".to_string(),
+        }
+    }
+}
+
+/// Shown instead of the editor when the data file exists but cannot be loaded.
+#[derive(Debug)]
+pub struct RecoveryScreen {
+    pub error: DataError,
+    actions: Vec<RecoveryAction>,
+    selected: usize,
+}
+
+impl RecoveryScreen {
+    pub fn new(error: DataError, latest_backup: Option<Backup>) -> Self {
+        let mut actions = vec![RecoveryAction::OpenAnyway];
+        if let Some(backup) = latest_backup {
+            actions.push(RecoveryAction::RestoreBackup(backup));
+        }
+        actions.push(RecoveryAction::StartFromTemplate);
+        actions.push(RecoveryAction::Quit);
+
+        Self {
+            error,
+            actions,
+            selected: 0,
+        }
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.actions.len();
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = (self.selected + self.actions.len() - 1) % self.actions.len();
+    }
+
+    pub fn selected_action(&self) -> RecoveryAction {
+        self.actions[self.selected].clone()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "The resume data could not be loaded:",
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        for line in self.error.to_string().lines() {
+            lines.push(Line::from(Span::styled(format!("  {}", line), Style::default().fg(Color::Red))));
+        }
+        lines.push(Line::from(""));
+
+        for (index, action) in self.actions.iter().enumerate() {
+            let line = if index == self.selected {
+                Span::styled(
+                    format!("> {}", action.title()),
+                    Style::default().fg(Color::LightMagenta).add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Span::raw(format!("  {}", action.title()))
+            };
+            lines.push(Line::from(line));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Up/Down to choose, Enter to continue, Esc to quit. Nothing is written until you save.",
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title("Resume Builder")
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Magenta)),
+            )
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(paragraph, area);
+    }
+}
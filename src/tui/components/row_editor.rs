@@ -0,0 +1,516 @@
+use crate::data::manager::{Point, Profile, Skill};
+use crate::tui::components::text_input::TextInput;
+use crossterm::event::KeyEvent;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Something the row editor holds one of per row: a line of text plus a
+/// second value edited with `t`, such as a bullet's tags.
+pub trait Row: Clone + Default {
+    /// What one row is called, singular and plural.
+    const NOUN: (&'static str, &'static str);
+    /// What the second value is called.
+    const DETAIL: &'static str;
+
+    fn text(&self) -> &str;
+    fn set_text(&mut self, text: String);
+    /// The second value as it is typed.
+    fn detail(&self) -> String;
+    fn set_detail(&mut self, value: &str);
+    /// The second value as shown after the text, if it is set.
+    fn detail_label(&self) -> Option<String>;
+}
+
+impl Row for Point {
+    const NOUN: (&'static str, &'static str) = ("bullet", "bullets");
+    const DETAIL: &'static str = "tags";
+
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+
+    fn detail(&self) -> String {
+        self.tags.join(", ")
+    }
+
+    fn set_detail(&mut self, value: &str) {
+        self.tags = value
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+
+    fn detail_label(&self) -> Option<String> {
+        (!self.tags.is_empty()).then(|| format!("[{}]", self.tags.join(", ")))
+    }
+}
+
+impl Row for Skill {
+    const NOUN: (&'static str, &'static str) = ("skill", "skills");
+    const DETAIL: &'static str = "level";
+
+    fn text(&self) -> &str {
+        &self.name
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.name = text;
+    }
+
+    fn detail(&self) -> String {
+        self.proficiency.clone().unwrap_or_default()
+    }
+
+    fn set_detail(&mut self, value: &str) {
+        let value = value.trim();
+        self.proficiency = (!value.is_empty()).then(|| value.to_string());
+    }
+
+    fn detail_label(&self) -> Option<String> {
+        self.proficiency.as_ref().map(|level| format!("({})", level))
+    }
+}
+
+impl Row for Profile {
+    const NOUN: (&'static str, &'static str) = ("link", "links");
+    const DETAIL: &'static str = "label";
+
+    fn text(&self) -> &str {
+        &self.url
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.url = text;
+    }
+
+    fn detail(&self) -> String {
+        self.label.clone()
+    }
+
+    fn set_detail(&mut self, value: &str) {
+        self.label = value.trim().to_string();
+    }
+
+    fn detail_label(&self) -> Option<String> {
+        (!self.label.is_empty()).then(|| format!("({})", self.label))
+    }
+}
+
+/// What is being typed into while a row is open for editing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Target {
+    Text,
+    Detail,
+}
+
+#[derive(Debug)]
+struct Draft {
+    target: Target,
+    value: TextInput,
+    /// The row was added for this edit and is dropped if it is cancelled.
+    is_new: bool,
+}
+
+/// Sub-editor for a list inside an entry, such as its bullet points, one
+/// row per item. The list is edited in place and handed back to the form
+/// when it is saved.
+#[derive(Debug)]
+pub struct RowEditor<T> {
+    rows: Vec<T>,
+    /// Selected row; `rows.len()` is the "+ Add" row.
+    selected: usize,
+    draft: Option<Draft>,
+    /// Whether the editor has the keyboard, as opposed to the form around it.
+    pub active: bool,
+}
+
+impl<T> Default for RowEditor<T> {
+    fn default() -> Self {
+        Self {
+            rows: Vec::new(),
+            selected: 0,
+            draft: None,
+            active: false,
+        }
+    }
+}
+
+impl<T: Row> RowEditor<T> {
+    pub fn new(rows: Vec<T>) -> Self {
+        Self {
+            rows,
+            ..Self::default()
+        }
+    }
+
+    pub fn rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    pub fn is_typing(&self) -> bool {
+        self.draft.is_some()
+    }
+
+    pub fn open(&mut self) {
+        self.active = true;
+        self.selected = self.selected.min(self.rows.len());
+    }
+
+    pub fn close(&mut self) {
+        self.draft = None;
+        self.active = false;
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % (self.rows.len() + 1);
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = (self.selected + self.rows.len()) % (self.rows.len() + 1);
+    }
+
+    /// Edits the selected row's text, or adds one on the "+ Add" row.
+    pub fn edit_selected(&mut self) {
+        match self.rows.get(self.selected) {
+            Some(row) => {
+                self.draft = Some(Draft {
+                    target: Target::Text,
+                    value: TextInput::new(row.text()),
+                    is_new: false,
+                });
+            }
+            None => self.add(),
+        }
+    }
+
+    /// Edits the selected row's second value, such as a bullet's tags.
+    pub fn edit_detail(&mut self) {
+        if let Some(row) = self.rows.get(self.selected) {
+            self.draft = Some(Draft {
+                target: Target::Detail,
+                value: TextInput::new(row.detail()),
+                is_new: false,
+            });
+        }
+    }
+
+    /// Inserts an empty row after the selected one and starts editing it.
+    pub fn add(&mut self) {
+        let index = (self.selected + 1).min(self.rows.len());
+        self.rows.insert(index, T::default());
+        self.selected = index;
+        self.draft = Some(Draft {
+            target: Target::Text,
+            value: TextInput::default(),
+            is_new: true,
+        });
+    }
+
+    pub fn remove(&mut self) {
+        if self.selected < self.rows.len() {
+            self.rows.remove(self.selected);
+            self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+        }
+    }
+
+    pub fn duplicate(&mut self) {
+        if let Some(row) = self.rows.get(self.selected).cloned() {
+            self.rows.insert(self.selected + 1, row);
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.selected > 0 && self.selected < self.rows.len() {
+            self.rows.swap(self.selected, self.selected - 1);
+            self.selected -= 1;
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.rows.swap(self.selected, self.selected + 1);
+            self.selected += 1;
+        }
+    }
+
+    /// Passes an editing key to the row being typed. Returns false if it
+    /// was not used.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match &mut self.draft {
+            Some(draft) => draft.value.handle_key(key),
+            None => false,
+        }
+    }
+
+    /// Keeps the typed value. A row whose text is left empty is removed.
+    pub fn confirm(&mut self) {
+        let Some(draft) = self.draft.take() else {
+            return;
+        };
+        if draft.target == Target::Text && draft.value.value().trim().is_empty() {
+            self.remove();
+            return;
+        }
+        let Some(row) = self.rows.get_mut(self.selected) else {
+            return;
+        };
+        match draft.target {
+            Target::Text => row.set_text(draft.value.value().trim().to_string()),
+            Target::Detail => row.set_detail(draft.value.value()),
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        if let Some(draft) = self.draft.take()
+            && draft.is_new
+        {
+            self.remove();
+        }
+    }
+
+    /// "3 bullets", used for the collapsed form row.
+    pub fn summary(&self) -> String {
+        let (one, many) = T::NOUN;
+        match self.rows.len() {
+            0 => format!("no {}", many),
+            1 => format!("1 {}", one),
+            n => format!("{} {}", n, many),
+        }
+    }
+
+    /// One line per row, for a pane `width` columns wide.
+    pub fn render(&self, width: usize) -> Vec<Line<'_>> {
+        let mut lines = Vec::new();
+        for (i, row) in self.rows.iter().enumerate() {
+            let is_selected = self.active && i == self.selected;
+            let style = if is_selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let detail_style = Style::default().fg(Color::DarkGray);
+            let marker = if is_selected { ">" } else { " " };
+            let prefix = format!("  {} {}. ", marker, i + 1);
+            let field_width = width.saturating_sub(prefix.width());
+            let mut spans = vec![Span::styled(prefix, style)];
+
+            match self.draft.as_ref().filter(|_| is_selected) {
+                Some(draft) if draft.target == Target::Text => {
+                    spans.extend(draft.value.render(field_width, true, style));
+                }
+                Some(draft) => {
+                    let label = format!("[{}: ", T::DETAIL);
+                    let used = row.text().width() + 1 + label.width() + 1;
+                    spans.push(Span::styled(format!("{} ", row.text()), style));
+                    spans.push(Span::styled(label, detail_style));
+                    spans.extend(draft.value.render(field_width.saturating_sub(used), true, detail_style));
+                    spans.push(Span::styled("]", detail_style));
+                }
+                None => {
+                    spans.push(Span::styled(row.text(), style));
+                    if let Some(detail) = row.detail_label() {
+                        spans.push(Span::styled(format!(" {}", detail), detail_style));
+                    }
+                }
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let add_style = if self.active && self.selected == self.rows.len() {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Green)
+        };
+        lines.push(Line::from(Span::styled(format!("    + Add {}", T::NOUN.0), add_style)));
+
+        if self.active {
+            let keys = if self.is_typing() {
+                "Enter to keep, Esc to cancel".to_string()
+            } else {
+                format!(
+                    "Enter edit, a add, d delete, c duplicate, K/J move up/down, t {}, Esc back to form",
+                    T::DETAIL
+                )
+            };
+            lines.push(Line::from(Span::styled(keys, Style::default().fg(Color::Cyan))));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn type_text<T: Row>(rows: &mut RowEditor<T>, text: &str) {
+        for c in text.chars() {
+            rows.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn skills_keep_separators_in_names_and_levels() {
+        let mut rows = RowEditor::<Skill>::default();
+        rows.open();
+        rows.edit_selected();
+        type_text(&mut rows, "C++ (17); STL");
+        rows.confirm();
+        rows.edit_detail();
+        type_text(&mut rows, "Daily (work)");
+        rows.confirm();
+
+        assert_eq!(
+            rows.rows(),
+            [Skill {
+                name: "C++ (17); STL".to_string(),
+                proficiency: Some("Daily (work)".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn clearing_a_level_removes_it() {
+        let mut rows = RowEditor::new(vec![Skill {
+            name: "Rust".to_string(),
+            proficiency: Some("Advanced".to_string()),
+        }]);
+        rows.open();
+        rows.edit_detail();
+        for _ in 0.."Advanced".len() {
+            rows.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        }
+        rows.confirm();
+        assert_eq!(rows.rows()[0].proficiency, None);
+    }
+
+    fn points(texts: &[&str]) -> RowEditor<Point> {
+        let mut rows = RowEditor::new(texts.iter().map(|&t| Point::from(t)).collect());
+        rows.open();
+        rows
+    }
+
+    fn texts(rows: &RowEditor<Point>) -> Vec<&str> {
+        rows.rows().iter().map(|p| p.text.as_str()).collect()
+    }
+
+    #[test]
+    fn edits_a_bullet_and_its_tags() {
+        let mut rows = points(&["Shipped things"]);
+        rows.edit_selected();
+        type_text(&mut rows, " weekly");
+        rows.confirm();
+        rows.edit_detail();
+        type_text(&mut rows, "backend, , lead ");
+        rows.confirm();
+
+        assert_eq!(
+            rows.rows(),
+            [Point {
+                text: "Shipped things weekly".to_string(),
+                tags: vec!["backend".to_string(), "lead".to_string()],
+            }]
+        );
+        assert_eq!(rows.summary(), "1 bullet");
+    }
+
+    #[test]
+    fn adds_after_the_selection_and_drops_empty_or_cancelled_rows() {
+        let mut rows = points(&["one", "three"]);
+        rows.add();
+        type_text(&mut rows, "two");
+        rows.confirm();
+        assert_eq!(texts(&rows), ["one", "two", "three"]);
+
+        rows.add();
+        rows.cancel();
+        rows.add();
+        type_text(&mut rows, "   ");
+        rows.confirm();
+        assert_eq!(texts(&rows), ["one", "two", "three"]);
+
+        // Editing an existing row and cancelling leaves it alone.
+        rows.edit_selected();
+        type_text(&mut rows, "!");
+        rows.cancel();
+        assert_eq!(texts(&rows), ["one", "two", "three"]);
+    }
+
+    #[test]
+    fn the_add_row_adds_a_bullet() {
+        let mut rows = points(&["one"]);
+        rows.next();
+        rows.edit_selected();
+        assert!(rows.is_typing());
+        type_text(&mut rows, "two");
+        rows.confirm();
+        assert_eq!(texts(&rows), ["one", "two"]);
+    }
+
+    #[test]
+    fn deletes_duplicates_and_reorders() {
+        let mut rows = points(&["a", "b", "c"]);
+        rows.next();
+        rows.move_up();
+        assert_eq!(texts(&rows), ["b", "a", "c"]);
+        rows.move_up();
+        assert_eq!(texts(&rows), ["b", "a", "c"]);
+        rows.move_down();
+        rows.move_down();
+        assert_eq!(texts(&rows), ["a", "c", "b"]);
+        rows.move_down();
+        assert_eq!(texts(&rows), ["a", "c", "b"]);
+
+        rows.duplicate();
+        assert_eq!(texts(&rows), ["a", "c", "b", "b"]);
+        rows.remove();
+        rows.remove();
+        assert_eq!(texts(&rows), ["a", "c"]);
+        rows.remove();
+        rows.remove();
+        rows.remove();
+        assert!(rows.rows().is_empty());
+        assert_eq!(rows.summary(), "no bullets");
+    }
+
+    #[test]
+    fn selection_wraps_through_the_add_row() {
+        let mut rows = points(&["a", "b"]);
+        rows.previous();
+        rows.edit_selected();
+        type_text(&mut rows, "c");
+        rows.confirm();
+        assert_eq!(texts(&rows), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn profile_links_keep_semicolons_and_colons() {
+        let mut rows = RowEditor::<Profile>::default();
+        rows.open();
+        rows.edit_selected();
+        type_text(&mut rows, "https://example.com/a;b?x=1");
+        rows.confirm();
+        rows.edit_detail();
+        type_text(&mut rows, " Blog: notes; drafts ");
+        rows.confirm();
+
+        assert_eq!(
+            rows.rows(),
+            [Profile {
+                label: "Blog: notes; drafts".to_string(),
+                url: "https://example.com/a;b?x=1".to_string(),
+            }]
+        );
+        assert_eq!(rows.summary(), "1 link");
+    }
+}
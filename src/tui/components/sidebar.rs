@@ -12,6 +12,12 @@ pub struct Sidebar {
     items: Vec<MenuItem>,
 }
 
+impl Default for Sidebar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Sidebar {
     pub fn new() -> Self {
         Self {
@@ -19,7 +25,8 @@ impl Sidebar {
         }
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect, current_menu: &MenuItem, focus: &Focus) {
+    /// `dirty` is whether there are changes not yet saved to disk.
+    pub fn render(&self, frame: &mut Frame, area: Rect, current_menu: &MenuItem, focus: &Focus, dirty: bool) {
         let items: Vec<ListItem> = self
             .items
             .iter()
@@ -50,16 +57,18 @@ impl Sidebar {
             })
             .collect();
 
-        let title = if focus == &Focus::Sidebar {
-            "Resume Builder"
+        let title = "Resume Builder";
+        let file_state = if dirty {
+            Span::styled(" ● unsaved (Ctrl+S) ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
         } else {
-            "Resume Builder"
+            Span::styled(" saved ", Style::default().fg(Color::Green))
         };
 
         let list = List::new(items)
             .block(
                 Block::default()
                     .title(title)
+                    .title_bottom(Line::from(file_state))
                     .borders(Borders::ALL)
                     .style(if focus == &Focus::Sidebar {
                         Style::default().fg(Color::Magenta)
@@ -0,0 +1,389 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+use std::cell::Cell;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// A single-line text field that edits by grapheme cluster, so accented
+/// letters, emoji and combining marks move and delete as one character.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    /// Byte offset of the cursor, always on a grapheme boundary.
+    cursor: usize,
+    /// Byte offset of the other end of the selection, if one is active.
+    anchor: Option<usize>,
+    /// Index of the first visible grapheme; adjusted while rendering so the
+    /// cursor stays in view.
+    scroll: Cell<usize>,
+}
+
+impl TextInput {
+    /// A field holding `value` with the cursor at the end.
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self {
+            cursor: value.len(),
+            value,
+            ..Self::default()
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// The selected byte range, if any text is selected.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        let range = anchor.min(self.cursor)..anchor.max(self.cursor);
+        (!range.is_empty()).then_some(range)
+    }
+
+    /// Applies an editing key. Returns false for keys a text field does not
+    /// handle (Enter, Esc, Tab, Up, Down...) so the caller can act on them.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let word = ctrl || alt;
+
+        match key.code {
+            // AltGr arrives as Ctrl+Alt on some platforms, and types a character.
+            KeyCode::Char(c) if ctrl && alt => self.insert_char(c),
+            KeyCode::Char('a') if ctrl => self.select_all(),
+            KeyCode::Char('w') if ctrl => self.delete_word_back(),
+            KeyCode::Char('d') if alt => self.delete_word_forward(),
+            KeyCode::Char('b') if alt => self.word_left(shift),
+            KeyCode::Char('f') if alt => self.word_right(shift),
+            KeyCode::Char(_) if ctrl || alt => return false,
+            KeyCode::Char(c) => self.insert_char(c),
+            KeyCode::Backspace if word => self.delete_word_back(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete if word => self.delete_word_forward(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left if word => self.word_left(shift),
+            KeyCode::Left => self.left(shift),
+            KeyCode::Right if word => self.word_right(shift),
+            KeyCode::Right => self.right(shift),
+            KeyCode::Home => self.home(shift),
+            KeyCode::End => self.end(shift),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Types `c`, replacing the selection.
+    pub fn insert_char(&mut self, c: char) {
+        self.delete_selection();
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+        // A combining mark joins the previous grapheme; keep the cursor after it.
+        self.cursor = self.boundary_at_or_after(self.cursor);
+    }
+
+    pub fn backspace(&mut self) {
+        if !self.delete_selection() {
+            let start = self.previous_boundary(self.cursor);
+            self.remove(start..self.cursor);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if !self.delete_selection() {
+            let end = self.next_boundary(self.cursor);
+            self.remove(self.cursor..end);
+        }
+    }
+
+    pub fn delete_word_back(&mut self) {
+        if !self.delete_selection() {
+            let start = self.previous_word_start(self.cursor);
+            self.remove(start..self.cursor);
+        }
+    }
+
+    pub fn delete_word_forward(&mut self) {
+        if !self.delete_selection() {
+            let end = self.next_word_end(self.cursor);
+            self.remove(self.cursor..end);
+        }
+    }
+
+    pub fn left(&mut self, select: bool) {
+        match self.selection() {
+            Some(range) if !select => self.move_to(range.start, false),
+            _ => self.move_to(self.previous_boundary(self.cursor), select),
+        }
+    }
+
+    pub fn right(&mut self, select: bool) {
+        match self.selection() {
+            Some(range) if !select => self.move_to(range.end, false),
+            _ => self.move_to(self.next_boundary(self.cursor), select),
+        }
+    }
+
+    pub fn word_left(&mut self, select: bool) {
+        self.move_to(self.previous_word_start(self.cursor), select);
+    }
+
+    pub fn word_right(&mut self, select: bool) {
+        self.move_to(self.next_word_end(self.cursor), select);
+    }
+
+    pub fn home(&mut self, select: bool) {
+        self.move_to(0, select);
+    }
+
+    pub fn end(&mut self, select: bool) {
+        self.move_to(self.value.len(), select);
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.value.len();
+    }
+
+    /// The visible part of the value for a field `width` columns wide, with
+    /// the cursor and selection highlighted when `focused`.
+    pub fn render(&self, width: usize, focused: bool, style: Style) -> Vec<Span<'static>> {
+        let graphemes: Vec<(usize, &str)> = self.value.grapheme_indices(true).collect();
+        let cursor = graphemes.iter().position(|(i, _)| *i >= self.cursor).unwrap_or(graphemes.len());
+        let width = width.max(1);
+
+        // Scroll just far enough that the cursor, and the cell it sits on,
+        // fit in `width` columns.
+        let mut scroll = self.scroll.get().min(cursor);
+        while scroll < cursor
+            && graphemes[scroll..cursor].iter().map(|(_, g)| g.width()).sum::<usize>() + 1 > width
+        {
+            scroll += 1;
+        }
+        self.scroll.set(scroll);
+
+        let selection = self.selection().filter(|_| focused);
+        let selected_style = style.bg(Color::Blue);
+        let cursor_style = style.add_modifier(Modifier::REVERSED);
+
+        let mut spans = Vec::new();
+        let mut used = 0;
+        for (index, (offset, grapheme)) in graphemes.iter().enumerate().skip(scroll) {
+            let grapheme_width = grapheme.width();
+            if used + grapheme_width > width {
+                break;
+            }
+            used += grapheme_width;
+            let grapheme_style = if focused && index == cursor {
+                cursor_style
+            } else if selection.as_ref().is_some_and(|range| range.contains(offset)) {
+                selected_style
+            } else {
+                style
+            };
+            spans.push(Span::styled(grapheme.to_string(), grapheme_style));
+        }
+        if focused && cursor == graphemes.len() && used < width {
+            spans.push(Span::styled(" ", cursor_style));
+        }
+        spans
+    }
+
+    fn move_to(&mut self, position: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = position;
+    }
+
+    fn remove(&mut self, range: Range<usize>) {
+        self.cursor = range.start;
+        self.value.replace_range(range, "");
+        self.anchor = None;
+    }
+
+    /// Removes the selected text. Returns whether there was any.
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(range) => {
+                self.remove(range);
+                true
+            }
+            None => {
+                self.anchor = None;
+                false
+            }
+        }
+    }
+
+    fn previous_boundary(&self, position: usize) -> usize {
+        self.value[..position].grapheme_indices(true).next_back().map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self, position: usize) -> usize {
+        self.value[position..]
+            .graphemes(true)
+            .next()
+            .map_or(position, |g| position + g.len())
+    }
+
+    fn boundary_at_or_after(&self, position: usize) -> usize {
+        self.value
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .find(|&i| i >= position)
+            .unwrap_or(self.value.len())
+    }
+
+    /// Start of the word before `position`, skipping any spaces or
+    /// punctuation in between.
+    fn previous_word_start(&self, position: usize) -> usize {
+        self.value[..position]
+            .split_word_bound_indices()
+            .rev()
+            .find(|(_, segment)| is_word(segment))
+            .map_or(0, |(i, _)| i)
+    }
+
+    /// End of the word after `position`, skipping any spaces or punctuation
+    /// in between.
+    fn next_word_end(&self, position: usize) -> usize {
+        self.value[position..]
+            .split_word_bound_indices()
+            .find(|(_, segment)| is_word(segment))
+            .map_or(self.value.len(), |(i, segment)| position + i + segment.len())
+    }
+}
+
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(char::is_alphanumeric)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn altgr_characters_are_typed() {
+        let mut input = TextInput::new("a");
+        assert!(input.handle_key(key(KeyCode::Char('@'), KeyModifiers::CONTROL | KeyModifiers::ALT)));
+        assert!(input.handle_key(key(KeyCode::Char('a'), KeyModifiers::CONTROL | KeyModifiers::ALT)));
+        assert_eq!(input.value(), "a@a");
+        assert!(!input.handle_key(key(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+    }
+
+    fn press(input: &mut TextInput, code: KeyCode) {
+        input.handle_key(key(code, KeyModifiers::NONE));
+    }
+
+    fn rendered(spans: &[Span]) -> String {
+        spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn edits_by_grapheme() {
+        // "e" + combining acute, a family emoji and a flag are one grapheme each.
+        let mut input = TextInput::new("cafe\u{301} 👨‍👩‍👧 🇫🇷");
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value(), "cafe\u{301} 👨‍👩‍👧 ");
+        for _ in 0..3 {
+            press(&mut input, KeyCode::Left);
+        }
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value(), "caf 👨‍👩‍👧 ");
+        press(&mut input, KeyCode::Home);
+        press(&mut input, KeyCode::Delete);
+        assert_eq!(input.value(), "af 👨‍👩‍👧 ");
+    }
+
+    #[test]
+    fn combining_marks_join_the_previous_character() {
+        let mut input = TextInput::new("");
+        input.insert_char('e');
+        input.insert_char('\u{301}');
+        input.insert_char('!');
+        assert_eq!(input.value(), "e\u{301}!");
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Left);
+        assert_eq!(input.cursor, 0);
+    }
+
+    #[test]
+    fn moves_and_deletes_by_word() {
+        let mut input = TextInput::new("Built the API, fast");
+        input.handle_key(key(KeyCode::Left, KeyModifiers::CONTROL));
+        assert_eq!(&input.value()[input.cursor..], "fast");
+        input.handle_key(key(KeyCode::Backspace, KeyModifiers::CONTROL));
+        assert_eq!(input.value(), "Built the fast");
+        input.handle_key(key(KeyCode::Char('d'), KeyModifiers::ALT));
+        assert_eq!(input.value(), "Built the ");
+        input.handle_key(key(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        assert_eq!(input.value(), "Built ");
+    }
+
+    #[test]
+    fn shift_selects_and_typing_replaces_the_selection() {
+        let mut input = TextInput::new("hello world");
+        input.handle_key(key(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::SHIFT));
+        assert_eq!(input.selection(), Some(6..11));
+        input.insert_char('W');
+        assert_eq!(input.value(), "hello W");
+        assert_eq!(input.selection(), None);
+
+        input.handle_key(key(KeyCode::Char('a'), KeyModifiers::CONTROL));
+        press(&mut input, KeyCode::Backspace);
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn arrows_collapse_a_selection_to_its_edge() {
+        let mut input = TextInput::new("abcdef");
+        press(&mut input, KeyCode::Home);
+        input.handle_key(key(KeyCode::Right, KeyModifiers::SHIFT));
+        input.handle_key(key(KeyCode::Right, KeyModifiers::SHIFT));
+        press(&mut input, KeyCode::Left);
+        assert_eq!((input.cursor, input.selection()), (0, None));
+        input.handle_key(key(KeyCode::End, KeyModifiers::SHIFT));
+        press(&mut input, KeyCode::Right);
+        assert_eq!((input.cursor, input.selection()), (6, None));
+    }
+
+    #[test]
+    fn leaves_navigation_keys_to_the_caller() {
+        let mut input = TextInput::new("x");
+        for code in [KeyCode::Enter, KeyCode::Esc, KeyCode::Tab, KeyCode::Up, KeyCode::Down] {
+            assert!(!input.handle_key(key(code, KeyModifiers::NONE)));
+        }
+        assert!(!input.handle_key(key(KeyCode::Char('z'), KeyModifiers::CONTROL)));
+        assert_eq!(input.value(), "x");
+    }
+
+    #[test]
+    fn scrolls_to_keep_the_cursor_visible() {
+        let style = Style::default();
+        let mut input = TextInput::new("abcdefghij");
+        // The cursor cell after the text takes one column.
+        assert_eq!(rendered(&input.render(5, true, style)), "ghij ");
+        press(&mut input, KeyCode::Home);
+        assert_eq!(rendered(&input.render(5, true, style)), "abcde");
+        // Wide characters are never split at the edge.
+        let input = TextInput::new("日本語テキスト");
+        let visible = rendered(&input.render(6, false, style));
+        assert!(visible.width() <= 6);
+    }
+}
+
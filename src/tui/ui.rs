@@ -1,23 +1,27 @@
 use crate::tui::app::{AppState, Focus};
+use crate::tui::components::row_editor::{Row, RowEditor};
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 
-pub fn run_app() -> io::Result<()> {
+pub fn run_app(file_path: &str, keep_history: bool) -> io::Result<()> {
+    // Load before entering raw mode so errors print to a normal terminal.
+    let mut app = AppState::new(file_path).map_err(|e| {
+        io::Error::other(format!("Failed to load resume data: {}", e))
+    })?;
+    if keep_history {
+        app.keep_history();
+    }
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
-    
-    let mut app = AppState::new("data/resume.json").map_err(|e| {
-        eprintln!("Failed to load resume data: {}", e);
-        io::Error::new(io::ErrorKind::Other, format!("{}", e))
-    })?;
 
     let result = run_event_loop(&mut terminal, &mut app);
 
@@ -35,71 +39,342 @@ fn run_event_loop(
     loop {
         terminal.draw(|frame| app.render(frame))?;
 
-        if let Event::Key(key_event) = event::read()? {
-            if key_event.kind == KeyEventKind::Press {
-                match key_event.code {
-                    KeyCode::Up => {
-                        app.previous_menu();
-                    }
-                    KeyCode::Down => {
-                        app.next_menu();
-                    }
-                    KeyCode::Enter => { // going into a sidebar option or content pane option
-                        app.select_current_menu();
-                    }
-                    KeyCode::Esc => { // either sub-content -> content or content -> sidebar
-                        match app.focus {
-                            Focus::Sidebar => (),
-                            Focus::Content => {
-                                if app.content_pane.is_editing {
-                                    app.content_pane.is_editing = false;
-                                    app.content_pane.selected_entry = None;
-                                    app.content_pane.entry_type = None;
-                                    if let Some(parent) = &app.content_pane.parent_menu {
-                                        app.current_menu = parent.clone();
-                                    } else {
-                                        app.select_current_menu();
-                                    }
-                                } else {
-                                    app.focus = Focus::Sidebar;
-                                }
-                            }
-                        }
-                    }
+        if let Event::Key(key_event) = event::read()?
+            && key_event.kind == KeyEventKind::Press
+        {
+            dispatch_key(app, key_event);
+        }
+
+        if app.should_exit {
+            break;
+        }
+    }
 
-                    KeyCode::Tab => {
-                        let new_focus = app.switch_focus();
-                        match app.focus {
-                            Focus::Content => {
-                                app.next_menu();
-                            }
-                            _ => {}
-                        }
+    Ok(())
+}
 
+/// Sends a key to whatever has the keyboard: an open dialog, a row editor
+/// or the main screen.
+fn dispatch_key(app: &mut AppState, key: KeyEvent) {
+    if app.recovery.is_some() {
+        handle_recovery_key(app, key.code);
+    } else if app.confirm.is_some() {
+        handle_confirm_key(app, key.code);
+    } else if app.prompt.is_some() {
+        handle_prompt_key(app, key);
+    } else if app.content_pane.rows_active() {
+        handle_rows_key(app, key);
+    } else {
+        handle_key(app, key);
+    }
+}
+
+fn handle_key(app: &mut AppState, key: KeyEvent) {
+    if app.content_pane.is_editing && app.handle_field_key(key) {
+        return;
+    }
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        match key.code {
+            KeyCode::Char('z') => return app.undo(),
+            KeyCode::Char('y') | KeyCode::Char('Z') => return app.redo(),
+            KeyCode::Char('s') => {
+                app.save();
+                return;
+            }
+            KeyCode::Char('o') => return app.request_save_as(),
+            KeyCode::Char('r') => return app.request_revert(),
+            _ => {}
+        }
+    }
+    match key.code {
+        KeyCode::Up => {
+            app.previous_menu();
+        }
+        KeyCode::Down => {
+            app.next_menu();
+        }
+        KeyCode::Enter => { // going into a sidebar option or content pane option
+            app.select_current_menu();
+        }
+        KeyCode::Esc => { // either sub-content -> content or content -> sidebar
+            match app.focus {
+                Focus::Sidebar => (),
+                Focus::Content => {
+                    if app.content_pane.is_editing {
+                        app.cancel_edit();
+                    } else {
+                        app.focus = Focus::Sidebar;
                     }
-                    KeyCode::Backspace => {
-                        app.handle_backspace();
-                    }
-                    KeyCode::Char(c) => {
-                        if app.content_pane.is_editing {
-                            app.handle_text_input(c);
-                        } else {
-                            if let Some(digit) = c.to_digit(10) {
-                                if digit >= 1 && digit <= 8 {
-                                    app.handle_number_input(digit as u8);
-                                }
-                            }
-                        }
-                    }
-                    _ => {}
                 }
             }
         }
 
-        if app.should_exit {
-            break;
+        KeyCode::Tab => {
+            app.switch_focus();
+            if app.focus == Focus::Content {
+                app.next_menu();
+            }
+        }
+        // Letters are shortcuts only on their own, so chords such as Ctrl+C
+        // don't duplicate or delete entries.
+        KeyCode::Char(c)
+            if !app.content_pane.is_editing
+                && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            if let Some(digit) = c.to_digit(10)
+                && (1..=9).contains(&digit)
+            {
+                app.handle_number_input(digit as u8);
+            } else {
+                app.handle_export_key(c);
+                app.handle_entry_key(c);
+            }
         }
+        KeyCode::Delete => app.request_delete_entry(),
+        _ => {}
     }
+}
 
-    Ok(())
+fn handle_rows_key(app: &mut AppState, key: KeyEvent) {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
+        // Keep the row being typed, then save as from anywhere else.
+        app.content_pane.confirm_rows();
+        app.save();
+        return;
+    }
+    let pane = &mut app.content_pane;
+    if let Some(points) = pane.points.as_mut().filter(|p| p.active) {
+        edit_rows(points, key);
+    } else if let Some(skills) = pane.skills.as_mut().filter(|s| s.active) {
+        edit_rows(skills, key);
+    } else if let Some(profiles) = pane.profiles.as_mut().filter(|p| p.active) {
+        edit_rows(profiles, key);
+    }
+}
+
+fn edit_rows<T: Row>(rows: &mut RowEditor<T>, key: KeyEvent) {
+    if rows.is_typing() {
+        if rows.handle_key(key) {
+            return;
+        }
+        match key.code {
+            KeyCode::Enter => rows.confirm(),
+            KeyCode::Esc => rows.cancel(),
+            _ => {}
+        }
+        return;
+    }
+    if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+        return;
+    }
+    match key.code {
+        KeyCode::Up => rows.previous(),
+        KeyCode::Down => rows.next(),
+        KeyCode::Enter => rows.edit_selected(),
+        KeyCode::Char('a') => rows.add(),
+        KeyCode::Char('d') | KeyCode::Delete => rows.remove(),
+        KeyCode::Char('c') => rows.duplicate(),
+        KeyCode::Char('K') => rows.move_up(),
+        KeyCode::Char('J') => rows.move_down(),
+        KeyCode::Char('t') => rows.edit_detail(),
+        KeyCode::Esc | KeyCode::Tab => rows.close(),
+        _ => {}
+    }
+}
+
+fn handle_confirm_key(app: &mut AppState, code: KeyCode) {
+    match code {
+        KeyCode::Char('y') | KeyCode::Enter => app.confirm(),
+        KeyCode::Char('n') => app.decline(),
+        KeyCode::Esc => app.cancel_confirm(),
+        _ => {}
+    }
+}
+
+fn handle_prompt_key(app: &mut AppState, key: KeyEvent) {
+    let Some(prompt) = &mut app.prompt else {
+        return;
+    };
+    if prompt.handle_key(key) {
+        return;
+    }
+    match key.code {
+        KeyCode::Enter => app.submit_prompt(),
+        KeyCode::Esc => app.cancel_prompt(),
+        _ => {}
+    }
+}
+
+fn handle_recovery_key(app: &mut AppState, code: KeyCode) {
+    let Some(recovery) = &mut app.recovery else {
+        return;
+    };
+    match code {
+        KeyCode::Up => recovery.previous(),
+        KeyCode::Down => recovery.next(),
+        KeyCode::Enter => app.recover(),
+        KeyCode::Esc | KeyCode::Char('q') => app.should_exit = true,
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::manager::Profile;
+    use crate::tui::app::MenuItem;
+    use crate::tui::components::content_pane::Status;
+    use std::fs;
+
+    fn press(app: &mut AppState, code: KeyCode, modifiers: KeyModifiers) {
+        dispatch_key(app, KeyEvent::new(code, modifiers));
+    }
+
+    #[test]
+    fn ctrl_s_saves_from_the_row_editor() {
+        let dir = std::env::temp_dir().join(format!("resume-utd-rows-{}", std::process::id()));
+        let file = dir.join("resume.json");
+        let mut app = AppState::new(file.to_str().unwrap()).unwrap();
+
+        // Skills, the "+ Add" entry, then the skills row of its form.
+        press(&mut app, KeyCode::Char('5'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        app.content_pane.selected_entry = Some(app.data_manager.resume.skills.len());
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        for c in "Tools".chars() {
+            press(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        press(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.content_pane.rows_active());
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        for c in "Git; (CLI)".chars() {
+            press(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+
+        assert!(!app.is_dirty());
+        let saved = fs::read_to_string(&file).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(saved.contains("Git; (CLI)"), "{}", saved);
+    }
+
+    #[test]
+    fn ctrl_chords_are_not_entry_shortcuts() {
+        let dir = std::env::temp_dir().join(format!("resume-utd-chords-{}", std::process::id()));
+        let file = dir.join("resume.json");
+        let mut app = AppState::new(file.to_str().unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        // Projects, with its first entry selected.
+        press(&mut app, KeyCode::Char('4'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        app.content_pane.selected_entry = Some(0);
+        let projects = app.data_manager.resume.projects.clone();
+        assert!(!projects.is_empty());
+
+        for c in ['c', 'd', 'J', 'K'] {
+            press(&mut app, KeyCode::Char(c), KeyModifiers::CONTROL);
+            press(&mut app, KeyCode::Char(c), KeyModifiers::ALT);
+        }
+        assert_eq!(app.data_manager.resume.projects, projects);
+        assert!(app.confirm.is_none());
+
+        press(&mut app, KeyCode::Char('c'), KeyModifiers::NONE);
+        assert_eq!(app.data_manager.resume.projects.len(), projects.len() + 1);
+    }
+
+    #[test]
+    fn ctrl_chords_are_not_row_shortcuts() {
+        let dir = std::env::temp_dir().join(format!("resume-utd-row-chords-{}", std::process::id()));
+        let file = dir.join("resume.json");
+        let mut app = AppState::new(file.to_str().unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        // The first project's form, then its bullet points.
+        press(&mut app, KeyCode::Char('4'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        app.content_pane.selected_entry = Some(0);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.content_pane.rows_active());
+        let points = app.content_pane.points.as_ref().unwrap().rows().to_vec();
+        assert!(!points.is_empty());
+
+        for c in ['a', 'c', 'd', 'J'] {
+            press(&mut app, KeyCode::Char(c), KeyModifiers::CONTROL);
+            press(&mut app, KeyCode::Char(c), KeyModifiers::ALT);
+        }
+        let rows = app.content_pane.points.as_ref().unwrap();
+        assert_eq!(rows.rows(), points);
+        assert!(!rows.is_typing());
+    }
+
+    #[test]
+    fn profile_links_are_edited_one_row_each() {
+        let dir = std::env::temp_dir().join(format!("resume-utd-links-{}", std::process::id()));
+        let file = dir.join("resume.json");
+        let mut app = AppState::new(file.to_str().unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let before = app.data_manager.resume.contact.profiles.clone();
+        let type_text = |app: &mut AppState, text: &str| {
+            for c in text.chars() {
+                press(app, KeyCode::Char(c), KeyModifiers::NONE);
+            }
+        };
+
+        // Personal Info's form, then the "+ Add link" row of its profiles.
+        press(&mut app, KeyCode::Char('1'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        type_text(&mut app, "Ada");
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        }
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.content_pane.rows_active());
+        press(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        type_text(&mut app, "https://example.com/a;b");
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        type_text(&mut app, "Blog: notes; drafts");
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+
+        // Website is the last field, so Enter applies the form.
+        press(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!app.content_pane.is_editing);
+
+        let profiles = &app.data_manager.resume.contact.profiles;
+        assert_eq!(profiles[..before.len()], before);
+        assert_eq!(
+            profiles[before.len()..],
+            [Profile {
+                label: "Blog: notes; drafts".to_string(),
+                url: "https://example.com/a;b".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn exporting_with_a_missing_profile_reports_it() {
+        let dir = std::env::temp_dir().join(format!("resume-utd-export-{}", std::process::id()));
+        let file = dir.join("resume.json");
+        let mut app = AppState::new(file.to_str().unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        app.content_pane.export_profile = Some("deleted".to_string());
+        app.current_menu = MenuItem::Export;
+        app.focus = Focus::Content;
+        press(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+        match &app.content_pane.export_status {
+            Some(Status::Error(message)) => assert!(message.contains("no profile named 'deleted'"), "{}", message),
+            other => panic!("expected an export error, got {:?}", other),
+        }
+    }
 }